@@ -2,12 +2,22 @@
 
 use criterion::{criterion_group, criterion_main, Criterion};
 
-use pasta_curves::arithmetic::CurveExt;
+#[cfg(feature = "bits")]
+use ff::PrimeFieldBits;
+use ff::{Field, WithSmallOrderMulGroup};
+#[cfg(feature = "bits")]
+use pasta_curves::arithmetic::mul_wnaf;
+use pasta_curves::arithmetic::{best_multiexp, CurveExt, FixedBaseMsm};
 use pasta_curves::{pallas, vesta};
 
 fn criterion_benchmark(c: &mut Criterion) {
     point_bench::<pallas::Point>(c, "Pallas");
     point_bench::<vesta::Point>(c, "Vesta");
+    #[cfg(feature = "bits")]
+    {
+        wnaf_bench::<pallas::Point>(c, "Pallas");
+        wnaf_bench::<vesta::Point>(c, "Vesta");
+    }
 }
 
 fn point_bench<C: CurveExt>(c: &mut Criterion, name: &str) {
@@ -22,6 +32,11 @@ fn point_bench<C: CurveExt>(c: &mut Criterion, name: &str) {
 
     group.bench_function("point subtraction", |bencher| bencher.iter(|| a - b));
 
+    let k = C::ScalarExt::ZETA.invert().unwrap();
+    group.bench_function("point scalar multiplication", |bencher| {
+        bencher.iter(|| a * k)
+    });
+
     group.bench_function("point to_bytes", |bencher| bencher.iter(|| a.to_bytes()));
 
     let repr = a.to_bytes();
@@ -38,6 +53,45 @@ fn point_bench<C: CurveExt>(c: &mut Criterion, name: &str) {
             bencher.iter(|| C::batch_normalize(input.as_slice(), output.as_mut_slice()));
         });
     }
+
+    for &n in [100, 1000, 10000].iter() {
+        let mut bases = vec![C::AffineExt::default(); n];
+        C::batch_normalize(&vec![a; n], &mut bases);
+        let scalars: Vec<_> = (0..n as u64)
+            .map(|i| {
+                C::ScalarExt::from(i)
+                    .square()
+                    .invert()
+                    .unwrap_or(C::ScalarExt::ONE)
+            })
+            .collect();
+        group.bench_function(format!("point best_multiexp/{}", n), |bencher| {
+            bencher.iter(|| best_multiexp(&scalars, &bases));
+        });
+
+        let msm = FixedBaseMsm::new(&bases);
+        group.bench_function(format!("point FixedBaseMsm::msm/{}", n), |bencher| {
+            bencher.iter(|| msm.msm(&scalars));
+        });
+    }
+}
+
+/// Benchmarks `mul_wnaf` for each window size, with the same point and scalar as
+/// "point scalar multiplication" so that the results can be compared.
+#[cfg(feature = "bits")]
+fn wnaf_bench<C: CurveExt>(c: &mut Criterion, name: &str)
+where
+    C::ScalarExt: PrimeFieldBits,
+{
+    let mut group = c.benchmark_group(name);
+
+    let a = C::generator();
+    let k = C::ScalarExt::ZETA.invert().unwrap();
+    for window in 2..=6 {
+        group.bench_function(format!("point mul_wnaf/{}", window), |bencher| {
+            bencher.iter(|| mul_wnaf(&a, &k, window))
+        });
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,273 @@
+#!/usr/bin/env python3
+"""Computes the hash-to-curve test vectors for Pallas and Vesta.
+
+This is a direct transcription of RFC 9380 (expand_message_xmd, expand_message_xof,
+hash_to_field, the simplified SWU map for AB == 0 and the isogeny map), using
+only the Python 3 standard library, so that the vectors in the tests can be
+checked without trusting the crate's implementation. It follows the RFC
+pseudocode rather than the optimized formulas in src/hashtocurve.rs.
+
+The curve parameters are those of the crate: Pallas and Vesta are
+y^2 = x^3 + 5, and the simplified SWU map is applied to the 3-isogenous curves
+y^2 = x^3 + A' * x + 1265 with Z = -13.
+
+Run it with `python3 scripts/hash_to_curve_vectors.py`.
+"""
+
+import hashlib
+
+P = 0x40000000000000000000000000000000224698FC094CF91B992D30ED00000001
+Q = 0x40000000000000000000000000000000224698FC0994A8DD8C46EB2100000001
+
+# The coefficient a of the isogenous curves (iso-Pallas over Fp, iso-Vesta over Fq).
+ISO_A = {
+    "pallas": 0x18354A2EB0EA8C9C49BE2D7258370742B74134581A27A59F92BB4B0B657A014B,
+    "vesta": 0x267F9B2EE592271A81639C4D96F787739673928C7D01B212C515AD7242EAA6B1,
+}
+ISO_B = 1265
+Z = -13
+
+# The coefficients of the isogeny maps from the isogenous curves, in the order
+# used by `Ep::ISOGENY_CONSTANTS` and `Eq::ISOGENY_CONSTANTS`:
+#
+#   x = (c0 x'^3 + c1 x'^2 + c2 x' + c3) / (x'^2 + c4 x' + c5)
+#   y = y' (c6 x'^3 + c7 x'^2 + c8 x' + c9) / (x'^3 + c10 x'^2 + c11 x' + c12)
+ISOGENY = {
+    "pallas": [
+        0x0E38E38E38E38E38E38E38E38E38E38E4081775473D8375B775F6034AAAAAAAB,
+        0x3509AFD51872D88E267C7FFA51CF412A0F93B82EE4B994958CF863B02814FB76,
+        0x17329B9EC525375398C7D7AC3D98FD13380AF066CFEB6D690EB64FAEF37EA4F7,
+        0x1C71C71C71C71C71C71C71C71C71C71C8102EEA8E7B06EB6EEBEC06955555580,
+        0x1D572E7DDC099CFF5A607FCCE0494A799C434AC1C96B6980C47F2AB668BCD71F,
+        0x325669BECAECD5D11D13BF2A7F22B105B4ABF9FB9A1FC81C2AA3AF1EAE5B6604,
+        0x1A12F684BDA12F684BDA12F684BDA12F7642B01AD461BAD25AD985B5E38E38E4,
+        0x1A84D7EA8C396C47133E3FFD28E7A09507C9DC17725CCA4AC67C31D8140A7DBB,
+        0x3FB98FF0D2DDCADD303216CCE1DB9FF11765E924F745937802E2BE87D225B234,
+        0x025ED097B425ED097B425ED097B425ED0AC03E8E134EB3E493E53AB371C71C4F,
+        0x0C02C5BCCA0E6B7F0790BFB3506DEFB65941A3A4A97AA1B35A28279B1D1B42AE,
+        0x17033D3C60C68173573B3D7F7D681310D976BBFABBC5661D4D90AB820B12320A,
+        0x40000000000000000000000000000000224698FC094CF91B992D30ECFFFFFDE5,
+    ],
+    "vesta": [
+        0x38E38E38E38E38E38E38E38E38E38E390205DD51CFA0961A43CD42C800000001,
+        0x1D935247B4473D17ACECF10F5F7C09A2216B8861EC72BD5D8B95C6AAF703BCC5,
+        0x18760C7F7A9AD20DED7EE4A9CDF78F8FD59D03D23B39CB11AEAC67BBEB586A3D,
+        0x31C71C71C71C71C71C71C71C71C71C71E1C521A795AC8356FB539A6F0000002B,
+        0x0A2DE485568125D51454798A5B5C56B2A3AD678129B604D3B7284F7EAF21A2E9,
+        0x14735171EE5427780C621DE8B91C242A30CD6D53DF49D235F169C187D2533465,
+        0x12F684BDA12F684BDA12F684BDA12F685601F4709A8ADCB36BEF1642AAAAAAAB,
+        0x2EC9A923DA239E8BD6767887AFBE04D121D910AEFB03B31D8BEE58E5FB81DE63,
+        0x19B0D87E16E2578866D1466E9DE10E6497A3CA5C24E9EA634986913AB4443034,
+        0x1ED097B425ED097B425ED097B425ED098BC32D36FB21A6A38F64842C55555533,
+        0x2F44D6C801C1B8BF9E7EB64F890A820C06A767BFC35B5BAC58DFECCE86B2745E,
+        0x3D59F455CAFC7668252659BA2B546C7E926847FB9DDD76A1D43D449776F99D2F,
+        0x40000000000000000000000000000000224698FC0994A8DD8C46EB20FFFFFDE5,
+    ],
+}
+
+MODULUS = {"pallas": P, "vesta": Q}
+
+
+def i2osp(value, length):
+    return value.to_bytes(length, "big")
+
+
+def os2ip(data):
+    return int.from_bytes(data, "big")
+
+
+def strxor(a, b):
+    return bytes(x ^ y for x, y in zip(a, b))
+
+
+def expand_message_xmd(hash_fn, block_size, msg, dst, len_in_bytes):
+    """RFC 9380, section 5.3.1."""
+    b_in_bytes = hash_fn().digest_size
+    ell = -(-len_in_bytes // b_in_bytes)
+    assert ell <= 255 and len_in_bytes <= 65535 and len(dst) <= 255
+    dst_prime = dst + i2osp(len(dst), 1)
+    z_pad = i2osp(0, block_size)
+    l_i_b_str = i2osp(len_in_bytes, 2)
+    msg_prime = z_pad + msg + l_i_b_str + i2osp(0, 1) + dst_prime
+    b_0 = hash_fn(msg_prime).digest()
+    b = [hash_fn(b_0 + i2osp(1, 1) + dst_prime).digest()]
+    for i in range(2, ell + 1):
+        b.append(hash_fn(strxor(b_0, b[-1]) + i2osp(i, 1) + dst_prime).digest())
+    return b"".join(b)[:len_in_bytes]
+
+
+def expand_message_xof(xof_fn, msg, dst, len_in_bytes):
+    """RFC 9380, section 5.3.2."""
+    assert len_in_bytes <= 65535 and len(dst) <= 255
+    dst_prime = dst + i2osp(len(dst), 1)
+    msg_prime = msg + i2osp(len_in_bytes, 2) + dst_prime
+    return xof_fn(msg_prime).digest(len_in_bytes)
+
+
+def sha256_xmd(msg, dst, n):
+    return expand_message_xmd(hashlib.sha256, 64, msg, dst, n)
+
+
+def blake2b_xmd(msg, dst, n):
+    return expand_message_xmd(
+        lambda data=b"": hashlib.blake2b(data, digest_size=64), 128, msg, dst, n
+    )
+
+
+def shake128_xof(msg, dst, n):
+    return expand_message_xof(hashlib.shake_128, msg, dst, n)
+
+
+def hash_to_field(expand, p, msg, dst, count, length):
+    """RFC 9380, section 5.2, with m = 1 and L = length."""
+    uniform_bytes = expand(msg, dst, count * length)
+    return [
+        os2ip(uniform_bytes[length * i : length * (i + 1)]) % p for i in range(count)
+    ]
+
+
+def is_square(x, p):
+    return x % p == 0 or pow(x, (p - 1) // 2, p) == 1
+
+
+def sqrt(x, p):
+    """Tonelli-Shanks; returns some square root of the square x."""
+    x %= p
+    if x == 0:
+        return 0
+    s, t = 0, p - 1
+    while t % 2 == 0:
+        s, t = s + 1, t // 2
+    n = 2
+    while is_square(n, p):
+        n += 1
+    m, c, r, u = s, pow(n, t, p), pow(x, (t + 1) // 2, p), pow(x, t, p)
+    while u != 1:
+        i, u2 = 0, u
+        while u2 != 1:
+            i, u2 = i + 1, u2 * u2 % p
+        b = pow(c, 1 << (m - i - 1), p)
+        m, c, r, u = i, b * b % p, r * b % p, u * b * b % p
+    assert r * r % p == x
+    return r
+
+
+def sgn0(x, p):
+    return (x % p) % 2
+
+
+def inv0(x, p):
+    return pow(x, p - 2, p)
+
+
+def map_to_curve_simple_swu(u, a, b, p):
+    """RFC 9380, section 6.6.2; returns an affine point of y^2 = x^3 + a x + b."""
+    z = Z % p
+    tv1 = inv0(z * z * pow(u, 4, p) + z * u * u, p)
+    x1 = -b * inv0(a, p) * (1 + tv1) % p
+    if tv1 == 0:
+        x1 = b * inv0(z * a, p) % p
+    gx1 = (x1**3 + a * x1 + b) % p
+    x2 = z * u * u * x1 % p
+    gx2 = (x2**3 + a * x2 + b) % p
+    if is_square(gx1, p):
+        x, y = x1, sqrt(gx1, p)
+    else:
+        x, y = x2, sqrt(gx2, p)
+    if sgn0(u, p) != sgn0(y, p):
+        y = -y % p
+    return (x, y)
+
+
+def iso_map(point, c, p):
+    """Maps an affine point of the isogenous curve to the curve y^2 = x^3 + 5."""
+    if point is None:
+        return None
+    x, y = point
+    x_den = (x * x + c[4] * x + c[5]) % p
+    y_den = (x**3 + c[10] * x * x + c[11] * x + c[12]) % p
+    if x_den == 0 or y_den == 0:
+        return None
+    x_num = c[0] * x**3 + c[1] * x * x + c[2] * x + c[3]
+    y_num = y * (c[6] * x**3 + c[7] * x * x + c[8] * x + c[9])
+    return (x_num * inv0(x_den, p) % p, y_num * inv0(y_den, p) % p)
+
+
+def add(p1, p2, a, p):
+    """Adds affine points of y^2 = x^3 + a x + b, with None as the identity."""
+    if p1 is None:
+        return p2
+    if p2 is None:
+        return p1
+    (x1, y1), (x2, y2) = p1, p2
+    if x1 == x2:
+        if (y1 + y2) % p == 0:
+            return None
+        lam = (3 * x1 * x1 + a) * inv0(2 * y1, p) % p
+    else:
+        lam = (y2 - y1) * inv0(x2 - x1, p) % p
+    x3 = (lam * lam - x1 - x2) % p
+    return (x3, (lam * (x1 - x3) - y1) % p)
+
+
+def on_curve(point, a, b, p):
+    x, y = point
+    return (y * y - x**3 - a * x - b) % p == 0
+
+
+def hash_to_curve(curve, expand, msg, dst, length):
+    """RFC 9380, section 3, with the elements derived from `length` bytes each."""
+    p, a, c = MODULUS[curve], ISO_A[curve], ISOGENY[curve]
+    u = hash_to_field(expand, p, msg, dst, 2, length)
+    q0 = map_to_curve_simple_swu(u[0], a, ISO_B, p)
+    q1 = map_to_curve_simple_swu(u[1], a, ISO_B, p)
+    assert on_curve(q0, a, ISO_B, p) and on_curve(q1, a, ISO_B, p)
+    r = iso_map(add(q0, q1, a, p), c, p)
+    assert on_curve(r, 0, 5, p)
+    return r
+
+
+def fmt(x):
+    return "0x%064x" % x
+
+
+def main():
+    # The RFC 9380 suites, which derive each element from L = 48 bytes.
+    for curve in ["pallas", "vesta"]:
+        for suite, expand, messages in [
+            ("XMD:SHA-256", sha256_xmd, [b"", b"abc", b"abcdef0123456789"]),
+            ("XOF:SHAKE128", shake128_xof, [b"", b"abc"]),
+        ]:
+            dst = b"QUUX-V01-CS02-with-%s_%s_SSWU_RO_" % (
+                curve.encode(),
+                suite.encode(),
+            )
+            print(dst.decode())
+            for msg in messages:
+                x, y = hash_to_curve(curve, expand, msg, dst, 48)
+                print("  msg = %r" % msg)
+                print("    x = " + fmt(x))
+                print("    y = " + fmt(y))
+
+    # `CurveExt::hash_to_curve` and `hash_to_field`, which use BLAKE2b-512 and
+    # derive each element from 64 bytes.
+    for curve, msg in [("pallas", b"Trans rights now!"), ("vesta", b"hello")]:
+        dst = b"z.cash:test-%s_XMD:BLAKE2b_SSWU_RO_" % curve.encode()
+        x, y = hash_to_curve(curve, blake2b_xmd, msg, dst, 64)
+        print("%s, msg = %r" % (dst.decode(), msg))
+        print("    x = " + fmt(x))
+        print("    y = " + fmt(y))
+
+    dst = b"z.cash:test-pallas_XMD:BLAKE2b_SSWU_RO_"
+    print("hash_to_field, %s, msg = %r" % (dst.decode(), b"Trans rights now!"))
+    for u in hash_to_field(blake2b_xmd, P, b"Trans rights now!", dst, 3, 64):
+        print("    " + fmt(u))
+
+    dst = b"QUUX-V01-CS02-with-vesta_XMD:SHA-256_SSWU_RO_"
+    print("hash_to_field, %s, msg = %r" % (dst.decode(), b"abc"))
+    for u in hash_to_field(sha256_xmd, Q, b"abc", dst, 3, 48):
+        print("    " + fmt(u))
+
+
+if __name__ == "__main__":
+    main()
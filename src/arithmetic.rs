@@ -5,7 +5,23 @@
 //! upstreamed into the `ff` and `group` crates after some refactoring.
 
 mod curves;
+#[cfg(feature = "gpu")]
+mod engine;
+mod fft;
 mod fields;
+#[cfg(feature = "alloc")]
+mod msm;
+#[cfg(all(feature = "alloc", feature = "bits"))]
+mod recoding;
 
 pub use curves::*;
+#[cfg(feature = "gpu")]
+pub use engine::*;
+pub use fft::*;
+#[cfg(feature = "alloc")]
+pub use fields::batch_invert;
 pub(crate) use fields::*;
+#[cfg(feature = "alloc")]
+pub use msm::*;
+#[cfg(all(feature = "alloc", feature = "bits"))]
+pub use recoding::*;
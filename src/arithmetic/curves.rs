@@ -114,6 +114,13 @@ pub trait CurveAffine:
     /// curve.
     fn from_xy(x: Self::Base, y: Self::Base) -> CtOption<Self>;
 
+    /// Obtains a point given $(x, y)$, without checking that it is on the
+    /// curve.
+    ///
+    /// The caller must ensure that the point is on the curve; otherwise the result
+    /// is unspecified.
+    fn from_xy_unchecked(x: Self::Base, y: Self::Base) -> Self;
+
     /// Returns whether or not this element is on the curve; should
     /// always be true unless an "unchecked" API was used.
     fn is_on_curve(&self) -> Choice;
@@ -125,6 +132,32 @@ pub trait CurveAffine:
     fn b() -> Self::Base;
 }
 
+/// A curve in a 2-cycle of curves, such as Pallas and Vesta, where the base field
+/// of each curve is the scalar field of the other.
+///
+/// This lets code that is generic over one curve of the cycle refer to the other
+/// one, and to both of their fields, without repeating the relationship in
+/// where-clauses:
+///
+/// ```
+/// use pasta_curves::arithmetic::{CurveCycle, CurveExt};
+///
+/// /// Maps a base field element of `C` to a scalar of its sibling curve.
+/// fn to_sibling_scalar<C: CurveCycle>(x: C::Base) -> <C::Sibling as CurveExt>::ScalarExt {
+///     x
+/// }
+/// ```
+///
+/// Requires the `alloc` feature flag because of `hash_to_curve` on [`CurveExt`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait CurveCycle: CurveExt {
+    /// The other curve of the cycle, whose base field is the scalar field of this
+    /// curve and whose scalar field is the base field of this curve.
+    type Sibling: CurveCycle<Sibling = Self>
+        + CurveExt<Base = Self::ScalarExt, ScalarExt = Self::Base>;
+}
+
 /// The affine coordinates of a point on an elliptic curve.
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
@@ -179,3 +212,193 @@ impl<C: CurveAffine> ConditionallySelectable for Coordinates<C> {
         }
     }
 }
+
+/// The smallest number of points that [`batch_normalize`] normalizes in a single
+/// parallel task.
+#[cfg(feature = "multicore")]
+const NORMALIZE_CHUNK_SIZE: usize = 1 << 10;
+
+/// Converts the points of `p` to affine form, writing them to `q`.
+///
+/// This is [`group::Curve::batch_normalize`], which shares a single field inversion
+/// between all of the points, except that with the `multicore` feature flag `p` is
+/// split into one chunk per thread, each normalized with its own inversion.
+///
+/// # Panics
+///
+/// Panics if `p` and `q` have different lengths.
+pub fn batch_normalize<C>(p: &[C], q: &mut [C::AffineRepr])
+where
+    C: group::Curve,
+    C::AffineRepr: Send,
+{
+    assert_eq!(p.len(), q.len());
+
+    #[cfg(feature = "multicore")]
+    {
+        use rayon::prelude::*;
+
+        let num_threads = rayon::current_num_threads();
+        let chunk = ((p.len() + num_threads - 1) / num_threads).max(NORMALIZE_CHUNK_SIZE);
+        p.par_chunks(chunk)
+            .zip(q.par_chunks_mut(chunk))
+            .for_each(|(p, q)| C::batch_normalize(p, q));
+    }
+
+    #[cfg(not(feature = "multicore"))]
+    C::batch_normalize(p, q);
+}
+
+/// The smallest number of point additions that [`sum_affine`] and [`add_slices`]
+/// perform in a single parallel task.
+#[cfg(feature = "multicore")]
+const ADD_CHUNK_SIZE: usize = 1 << 10;
+
+/// Returns the sum of `points`, using mixed addition.
+///
+/// With the `multicore` feature flag, large slices are summed in parallel.
+pub fn sum_affine<C>(points: &[C::AffineRepr]) -> C
+where
+    C: group::Curve + Send,
+    C::AffineRepr: Sync,
+{
+    #[cfg(feature = "multicore")]
+    {
+        use rayon::prelude::*;
+
+        points
+            .par_chunks(ADD_CHUNK_SIZE)
+            .map(|points| points.iter().fold(C::identity(), |acc, p| acc + p))
+            .reduce(C::identity, |a, b| a + b)
+    }
+
+    #[cfg(not(feature = "multicore"))]
+    points.iter().fold(C::identity(), |acc, p| acc + p)
+}
+
+/// Adds each point of `b` to the corresponding point of `a`, using mixed addition.
+///
+/// With the `multicore` feature flag, large slices are processed in parallel.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn add_slices<C>(a: &mut [C], b: &[C::AffineRepr])
+where
+    C: group::Curve + Send,
+    C::AffineRepr: Sync,
+{
+    assert_eq!(a.len(), b.len());
+
+    #[cfg(feature = "multicore")]
+    {
+        use rayon::prelude::*;
+
+        a.par_chunks_mut(ADD_CHUNK_SIZE)
+            .zip(b.par_chunks(ADD_CHUNK_SIZE))
+            .for_each(|(a, b)| a.iter_mut().zip(b.iter()).for_each(|(a, b)| *a += b));
+    }
+
+    #[cfg(not(feature = "multicore"))]
+    a.iter_mut().zip(b.iter()).for_each(|(a, b)| *a += b);
+}
+
+/// Multiplies each point of `points` by `k`.
+///
+/// With the `multicore` feature flag, the points are processed in parallel.
+pub fn scale_slice<C>(points: &mut [C], k: &C::Scalar)
+where
+    C: group::Curve + Send,
+{
+    #[cfg(feature = "multicore")]
+    {
+        use rayon::prelude::*;
+
+        points.par_iter_mut().for_each(|p| *p *= k);
+    }
+
+    #[cfg(not(feature = "multicore"))]
+    points.iter_mut().for_each(|p| *p *= k);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ep, EpAffine};
+    use ff::Field;
+    use group::{Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_batch_normalize() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // Large enough to be split into several chunks when the `multicore` feature is
+        // enabled.
+        for n in [0, 1, 100, 5000] {
+            let mut p: std::vec::Vec<Ep> = (0..n).map(|_| Ep::random(&mut rng)).collect();
+            if n > 1 {
+                p[1] = Ep::identity();
+            }
+            let mut q = std::vec![EpAffine::default(); n];
+            batch_normalize(&p, &mut q);
+            for (p, q) in p.iter().zip(q.iter()) {
+                assert_eq!(p.to_affine(), *q);
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_curve_cycle() {
+        use crate::{Eq, Fp, Fq};
+
+        fn sibling_scalar<C: CurveCycle>(x: C::Base) -> <C::Sibling as CurveExt>::ScalarExt {
+            x
+        }
+
+        fn sibling_base<C: CurveCycle>(x: C::ScalarExt) -> <C::Sibling as CurveExt>::Base {
+            x
+        }
+
+        assert_eq!(sibling_scalar::<Ep>(Fp::one()), Fp::one());
+        assert_eq!(sibling_base::<Ep>(Fq::one()), Fq::one());
+        assert_eq!(sibling_scalar::<Eq>(Fq::one()), Fq::one());
+        assert_eq!(sibling_base::<Eq>(Fp::one()), Fp::one());
+    }
+
+    #[test]
+    fn test_slice_ops() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for n in [0, 1, 100, 3000] {
+            let a: std::vec::Vec<Ep> = (0..n).map(|_| Ep::random(&mut rng)).collect();
+            let b: std::vec::Vec<EpAffine> =
+                (0..n).map(|_| Ep::random(&mut rng).to_affine()).collect();
+
+            assert_eq!(sum_affine::<Ep>(&b), b.iter().sum::<Ep>());
+
+            let mut sums = a.clone();
+            add_slices(&mut sums, &b);
+            for ((sum, a), b) in sums.iter().zip(a.iter()).zip(b.iter()) {
+                assert_eq!(*sum, a + b);
+            }
+
+            if n <= 100 {
+                let k = crate::Fq::random(&mut rng);
+                let mut scaled = a.clone();
+                scale_slice(&mut scaled, &k);
+                for (scaled, a) in scaled.iter().zip(a.iter()) {
+                    assert_eq!(*scaled, a * k);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! This module provides extension points for offloading multi-scalar multiplication
+//! and FFTs to an external implementation, such as a GPU.
+//!
+//! Code that is generic over an [`MsmEngine`] or [`FftEngine`] can be given a
+//! hardware-accelerated engine from another crate, or [`CpuEngine`], which uses
+//! [`best_multiexp`], [`best_fft`] and [`best_ifft`] from this crate. The coordinate
+//! conversions below produce and consume the flat arrays of affine coordinates that
+//! are typically uploaded to and downloaded from a device.
+
+use alloc::vec::Vec;
+
+use ff::Field;
+use group::prime::PrimeCurveAffine;
+
+use super::{
+    batch_normalize, best_fft, best_ifft, best_multiexp, Coordinates, CurveAffine, CurveExt,
+    FftGroup,
+};
+
+/// An implementation of multi-scalar multiplication over the affine points `C`.
+pub trait MsmEngine<C: CurveAffine> {
+    /// Computes `sum_i coeffs[i] * bases[i]`.
+    ///
+    /// Implementations may panic if `coeffs` and `bases` have different lengths.
+    fn msm(&self, coeffs: &[C::ScalarExt], bases: &[C]) -> C::Curve;
+}
+
+/// An implementation of radix-2 FFTs over elements of `G`.
+pub trait FftEngine<Scalar: Field, G: FftGroup<Scalar>> {
+    /// Performs an in-place FFT over `a`, with the same contract as [`best_fft`].
+    fn fft(&self, a: &mut [G], omega: Scalar, log_n: u32);
+
+    /// Performs an in-place inverse FFT over `a`, with the same contract as
+    /// [`best_ifft`].
+    fn ifft(&self, a: &mut [G], omega_inv: Scalar, log_n: u32);
+}
+
+/// The engine that computes on the CPU with the implementations in this crate.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuEngine;
+
+impl<C: CurveAffine> MsmEngine<C> for CpuEngine {
+    fn msm(&self, coeffs: &[C::ScalarExt], bases: &[C]) -> C::Curve {
+        best_multiexp(coeffs, bases)
+    }
+}
+
+impl<Scalar: Field, G: FftGroup<Scalar>> FftEngine<Scalar, G> for CpuEngine {
+    fn fft(&self, a: &mut [G], omega: Scalar, log_n: u32) {
+        best_fft(a, omega, log_n)
+    }
+
+    fn ifft(&self, a: &mut [G], omega_inv: Scalar, log_n: u32) {
+        best_ifft(a, omega_inv, log_n)
+    }
+}
+
+/// Returns the affine coordinates of `points` as a flat array `[x_0, y_0, x_1, y_1,
+/// ...]`, with both coordinates of the identity set to zero.
+pub fn affine_coordinates<C: CurveAffine>(points: &[C]) -> Vec<C::Base> {
+    let mut coordinates = Vec::with_capacity(points.len() * 2);
+    for p in points {
+        let p: Option<Coordinates<C>> = p.coordinates().into();
+        let (x, y) = p.map_or((C::Base::ZERO, C::Base::ZERO), |p| (*p.x(), *p.y()));
+        coordinates.push(x);
+        coordinates.push(y);
+    }
+    coordinates
+}
+
+/// Normalizes `points` and returns their affine coordinates in the layout of
+/// [`affine_coordinates`].
+pub fn projective_to_affine_coordinates<C: CurveExt>(
+    points: &[C],
+) -> Vec<<C::AffineExt as CurveAffine>::Base> {
+    let mut affine = alloc::vec![C::AffineExt::identity(); points.len()];
+    batch_normalize(points, &mut affine);
+    affine_coordinates(&affine)
+}
+
+/// Decodes points from a flat array of affine coordinates in the layout of
+/// [`affine_coordinates`], returning `None` if its length is odd or any point is
+/// not on the curve.
+pub fn from_affine_coordinates<C: CurveAffine>(coordinates: &[C::Base]) -> Option<Vec<C>> {
+    if coordinates.len() % 2 != 0 {
+        return None;
+    }
+    coordinates
+        .chunks(2)
+        .map(|c| Option::from(C::from_xy(c[0], c[1])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use group::{Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    use crate::{Ep, EpAffine, Fp, Fq};
+
+    #[test]
+    fn test_cpu_engine() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        fn msm<E: MsmEngine<EpAffine>>(engine: &E, coeffs: &[Fq], bases: &[EpAffine]) -> Ep {
+            engine.msm(coeffs, bases)
+        }
+
+        let coeffs: Vec<Fq> = (0..50).map(|_| Fq::random(&mut rng)).collect();
+        let bases: Vec<EpAffine> = (0..50).map(|_| Ep::random(&mut rng).to_affine()).collect();
+        assert_eq!(
+            msm(&CpuEngine, &coeffs, &bases),
+            best_multiexp(&coeffs, &bases)
+        );
+
+        let log_n = 5;
+        let a: Vec<Fp> = (0..1 << log_n).map(|_| Fp::random(&mut rng)).collect();
+        let mut b = a.clone();
+        FftEngine::<Fp, Fp>::fft(
+            &CpuEngine,
+            &mut b,
+            Fp::ROOTS_OF_UNITY[log_n as usize],
+            log_n,
+        );
+        let mut c = a.clone();
+        best_fft(&mut c, Fp::ROOTS_OF_UNITY[log_n as usize], log_n);
+        assert_eq!(b, c);
+        FftEngine::<Fp, Fp>::ifft(
+            &CpuEngine,
+            &mut b,
+            Fp::ROOTS_OF_UNITY_INV[log_n as usize],
+            log_n,
+        );
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_affine_coordinates() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let mut points: Vec<Ep> = (0..10).map(|_| Ep::random(&mut rng)).collect();
+        points[3] = Ep::identity();
+        let mut affine = alloc::vec![EpAffine::identity(); points.len()];
+        Ep::batch_normalize(&points, &mut affine);
+
+        let coordinates = affine_coordinates(&affine);
+        assert_eq!(coordinates.len(), 20);
+        assert_eq!(coordinates[0], affine[0].x());
+        assert_eq!(coordinates[1], affine[0].y());
+        assert_eq!(&coordinates[6..8], &[Fp::ZERO, Fp::ZERO]);
+        assert_eq!(projective_to_affine_coordinates(&points), coordinates);
+
+        assert_eq!(from_affine_coordinates(&coordinates), Some(affine));
+        assert_eq!(from_affine_coordinates::<EpAffine>(&coordinates[1..]), None);
+        let mut invalid = coordinates;
+        invalid[1] += Fp::ONE;
+        assert_eq!(from_affine_coordinates::<EpAffine>(&invalid), None);
+    }
+}
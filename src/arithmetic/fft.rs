@@ -0,0 +1,448 @@
+//! This module provides radix-2 FFTs that operate generically over either a field or an
+//! elliptic curve group.
+
+use ff::{Field, WithSmallOrderMulGroup};
+use group::{GroupOps, GroupOpsOwned, ScalarMul, ScalarMulOwned};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "multicore")]
+use rayon::prelude::*;
+
+/// The number of butterflies in a single parallel task of [`best_fft`].
+#[cfg(feature = "multicore")]
+const BUTTERFLY_CHUNK_SIZE: usize = 1 << 10;
+
+/// This represents an element of a group with basic operations that can be performed.
+/// This allows an FFT implementation (for example) to operate generically over either a
+/// field or elliptic curve group.
+pub trait FftGroup<Scalar: Field>:
+    Copy + Send + Sync + 'static + GroupOps + GroupOpsOwned + ScalarMul<Scalar> + ScalarMulOwned<Scalar>
+{
+}
+
+impl<T, Scalar> FftGroup<Scalar> for T
+where
+    Scalar: Field,
+    T: Copy
+        + Send
+        + Sync
+        + 'static
+        + GroupOps
+        + GroupOpsOwned
+        + ScalarMul<Scalar>
+        + ScalarMulOwned<Scalar>,
+{
+}
+
+/// Performs an in-place radix-2 FFT over `a`, which must have length `2^log_n`.
+///
+/// `omega` must be a primitive `2^log_n`-th root of unity, such as
+/// `Fp::ROOTS_OF_UNITY[log_n]`. On return, `a[i]` holds the evaluation at `omega^i` of
+/// the polynomial whose coefficients were the original contents of `a`.
+///
+/// # Panics
+///
+/// Panics if `a.len() != 2^log_n`.
+pub fn best_fft<Scalar: Field, G: FftGroup<Scalar>>(a: &mut [G], omega: Scalar, log_n: u32) {
+    let n = a.len();
+    assert_eq!(n, 1 << log_n);
+
+    bit_reverse_permute(a);
+
+    let mut m = 1;
+    for _ in 0..log_n {
+        // w_m is a primitive (2m)-th root of unity.
+        let w_m = omega.pow_vartime(&[(n / (2 * m)) as u64]);
+
+        #[cfg(not(feature = "multicore"))]
+        for chunk in a.chunks_mut(2 * m) {
+            let (lo, hi) = chunk.split_at_mut(m);
+            butterflies(lo, hi, Scalar::ONE, w_m);
+        }
+
+        // Each layer is parallelized both across chunks and, for the later layers
+        // where there are only a few large chunks, within each chunk.
+        #[cfg(feature = "multicore")]
+        a.par_chunks_mut(2 * m).for_each(|chunk| {
+            let (lo, hi) = chunk.split_at_mut(m);
+            lo.par_chunks_mut(BUTTERFLY_CHUNK_SIZE)
+                .zip(hi.par_chunks_mut(BUTTERFLY_CHUNK_SIZE))
+                .enumerate()
+                .for_each(|(i, (lo, hi))| {
+                    let w = w_m.pow_vartime(&[(i * BUTTERFLY_CHUNK_SIZE) as u64]);
+                    butterflies(lo, hi, w, w_m);
+                });
+        });
+
+        m *= 2;
+    }
+}
+
+/// Performs an in-place radix-2 inverse FFT over `a`, which must have length `2^log_n`.
+///
+/// `omega_inv` must be the inverse of the `omega` passed to [`best_fft`], such as
+/// `Fp::ROOTS_OF_UNITY_INV[log_n]`. This undoes [`best_fft`], including the division
+/// by `2^log_n`.
+///
+/// # Panics
+///
+/// Panics if `a.len() != 2^log_n`.
+pub fn best_ifft<Scalar: Field, G: FftGroup<Scalar>>(a: &mut [G], omega_inv: Scalar, log_n: u32) {
+    best_fft(a, omega_inv, log_n);
+
+    let mut n = Scalar::ONE;
+    for _ in 0..log_n {
+        n = n.double();
+    }
+    let divisor = n.invert().unwrap();
+
+    #[cfg(not(feature = "multicore"))]
+    for x in a.iter_mut() {
+        *x *= divisor;
+    }
+
+    #[cfg(feature = "multicore")]
+    a.par_iter_mut().for_each(|x| *x *= divisor);
+}
+
+/// Performs an in-place FFT over the coset `ZETA * <omega>` of the multiplicative
+/// subgroup generated by `omega`.
+///
+/// On return, `a[i]` holds the evaluation at `ZETA * omega^i` of the polynomial whose
+/// coefficients were the original contents of `a`. `omega` and `log_n` are as for
+/// [`best_fft`].
+pub fn coset_fft<Scalar: WithSmallOrderMulGroup<3>, G: FftGroup<Scalar>>(
+    a: &mut [G],
+    omega: Scalar,
+    log_n: u32,
+) {
+    distribute_powers_zeta(a, Scalar::ZETA);
+    best_fft(a, omega, log_n);
+}
+
+/// Performs an in-place inverse FFT over the coset `ZETA * <omega>`, undoing
+/// [`coset_fft`].
+///
+/// `omega_inv` and `log_n` are as for [`best_ifft`].
+pub fn coset_ifft<Scalar: WithSmallOrderMulGroup<3>, G: FftGroup<Scalar>>(
+    a: &mut [G],
+    omega_inv: Scalar,
+    log_n: u32,
+) {
+    best_ifft(a, omega_inv, log_n);
+    // ZETA is a cube root of unity, so ZETA^-1 = ZETA^2.
+    distribute_powers_zeta(a, Scalar::ZETA.square());
+}
+
+/// Computes a low-degree extension of `values` by a factor of `blowup`.
+///
+/// `values` are the evaluations of a polynomial of degree less than `n = values.len()`
+/// over the multiplicative subgroup of order `n`. This returns the evaluations of the
+/// same polynomial over the coset `ZETA * H`, where `H` is the multiplicative subgroup
+/// of order `n * blowup`, in the order given by [`coset_fft`].
+///
+/// # Panics
+///
+/// Panics if `values.len()` or `blowup` is not a power of two, or if `n * blowup`
+/// exceeds `2^S`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn lde<Scalar: WithSmallOrderMulGroup<3>, G: FftGroup<Scalar>>(
+    values: &[G],
+    blowup: usize,
+) -> Vec<G> {
+    assert!(values.len().is_power_of_two());
+    assert!(blowup.is_power_of_two());
+    let log_n = values.len().trailing_zeros();
+    let log_extended = log_n + blowup.trailing_zeros();
+
+    let mut coeffs = values.to_vec();
+    best_ifft(
+        &mut coeffs,
+        root_of_unity::<Scalar>(log_n).invert().unwrap(),
+        log_n,
+    );
+
+    let zero = values[0] * Scalar::ZERO;
+    coeffs.resize(values.len() * blowup, zero);
+    coset_fft(
+        &mut coeffs,
+        root_of_unity::<Scalar>(log_extended),
+        log_extended,
+    );
+    coeffs
+}
+
+/// Returns a primitive `2^log_n`-th root of unity.
+#[cfg(feature = "alloc")]
+fn root_of_unity<F: ff::PrimeField>(log_n: u32) -> F {
+    assert!(log_n <= F::S);
+    let mut omega = F::ROOT_OF_UNITY;
+    for _ in log_n..F::S {
+        omega = omega.square();
+    }
+    omega
+}
+
+/// Multiplies `a[i]` by `zeta^i`, where `zeta` is a cube root of unity.
+fn distribute_powers_zeta<Scalar: Field, G: FftGroup<Scalar>>(a: &mut [G], zeta: Scalar) {
+    let coset_powers = [Scalar::ONE, zeta, zeta.square()];
+
+    #[cfg(not(feature = "multicore"))]
+    for (i, x) in a.iter_mut().enumerate() {
+        *x *= coset_powers[i % 3];
+    }
+
+    #[cfg(feature = "multicore")]
+    a.par_iter_mut()
+        .enumerate()
+        .for_each(|(i, x)| *x *= coset_powers[i % 3]);
+}
+
+/// Applies the butterflies `(x, y) -> (x + w^j y, x - w^j y)` to the `j`-th elements of
+/// `lo` and `hi`, where `w` starts at `w` and is multiplied by `w_m` for each `j`.
+fn butterflies<Scalar: Field, G: FftGroup<Scalar>>(
+    lo: &mut [G],
+    hi: &mut [G],
+    mut w: Scalar,
+    w_m: Scalar,
+) {
+    for (x, y) in lo.iter_mut().zip(hi.iter_mut()) {
+        let t = *y * w;
+        *y = *x - t;
+        *x += t;
+        w *= w_m;
+    }
+}
+
+/// `bit_reverse_permute` swaps tiles of `2^BIT_REVERSE_BLOCK_BITS x 2^BIT_REVERSE_BLOCK_BITS`
+/// elements at a time.
+const BIT_REVERSE_BLOCK_BITS: u32 = 5;
+
+/// Permutes `a` in place so that the element at index `i` moves to index `rev(i)`, where
+/// `rev` reverses the low `log2(a.len())` bits of `i`.
+///
+/// For large slices, the permutation is applied in tiles so that both the source and
+/// destination of each swap stay in a small working set of cache lines.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not a power of two.
+pub fn bit_reverse_permute<T>(a: &mut [T]) {
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    let log_n = n.trailing_zeros();
+
+    let rev = |i: usize, bits: u32| {
+        if bits == 0 {
+            0
+        } else {
+            i.reverse_bits() >> (usize::BITS - bits)
+        }
+    };
+
+    let b = BIT_REVERSE_BLOCK_BITS;
+    if log_n < 2 * b {
+        for i in 0..n {
+            let r = rev(i, log_n);
+            if i < r {
+                a.swap(i, r);
+            }
+        }
+        return;
+    }
+
+    // Split each index into (hi, mid, lo), where hi and lo have b bits. Then
+    // rev(hi, mid, lo) = (rev(lo), rev(mid), rev(hi)), so the tile of indices with a
+    // given mid is swapped with the tile with mid' = rev(mid).
+    let mid_bits = log_n - 2 * b;
+    for mid in 0..1 << mid_bits {
+        let rev_mid = rev(mid, mid_bits);
+        if rev_mid < mid {
+            // This tile was already swapped with tile rev_mid.
+            continue;
+        }
+
+        for hi in 0..1 << b {
+            for lo in 0..1 << b {
+                let i = (hi << (b + mid_bits)) | (mid << b) | lo;
+                let j = (rev(lo, b) << (b + mid_bits)) | (rev_mid << b) | rev(hi, b);
+                if mid != rev_mid || i < j {
+                    a.swap(i, j);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ep, Fp, Fq};
+    use ff::WithSmallOrderMulGroup;
+    use group::Group;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    /// Evaluates the polynomial with coefficients `a` at `omega^i` for each `i`.
+    fn naive_dft<Scalar: Field, G: FftGroup<Scalar>>(a: &[G], omega: Scalar) -> std::vec::Vec<G> {
+        (0..a.len())
+            .map(|i| {
+                let x = omega.pow_vartime(&[i as u64]);
+                let mut acc = a[0] * Scalar::ZERO;
+                for c in a.iter().rev() {
+                    acc = acc * x + c;
+                }
+                acc
+            })
+            .collect()
+    }
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn test_fft_fp() {
+        let mut rng = rng();
+        for log_n in 0..7 {
+            let a: std::vec::Vec<Fp> = (0..1 << log_n).map(|_| Fp::random(&mut rng)).collect();
+
+            let mut b = a.clone();
+            best_fft(&mut b, Fp::ROOTS_OF_UNITY[log_n], log_n as u32);
+            assert_eq!(b, naive_dft(&a, Fp::ROOTS_OF_UNITY[log_n]));
+
+            best_ifft(&mut b, Fp::ROOTS_OF_UNITY_INV[log_n], log_n as u32);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_fft_fp_large() {
+        // Large enough that the later layers are split into several parallel tasks
+        // when the `multicore` feature is enabled.
+        let log_n = 12;
+        let mut rng = rng();
+        let a: std::vec::Vec<Fp> = (0..1 << log_n).map(|_| Fp::random(&mut rng)).collect();
+
+        let mut b = a.clone();
+        best_fft(&mut b, Fp::ROOTS_OF_UNITY[log_n], log_n as u32);
+        for i in [0, 1, 1000, (1 << log_n) - 1] {
+            let x = Fp::ROOTS_OF_UNITY[log_n].pow_vartime(&[i as u64]);
+            let expected = a.iter().rev().fold(Fp::zero(), |acc, c| acc * x + c);
+            assert_eq!(b[i], expected);
+        }
+
+        best_ifft(&mut b, Fp::ROOTS_OF_UNITY_INV[log_n], log_n as u32);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_fft_ep() {
+        let mut rng = rng();
+        for log_n in 0..5 {
+            let a: std::vec::Vec<Ep> = (0..1 << log_n).map(|_| Ep::random(&mut rng)).collect();
+
+            let mut b = a.clone();
+            best_fft(&mut b, Fq::ROOTS_OF_UNITY[log_n], log_n as u32);
+            assert_eq!(b, naive_dft(&a, Fq::ROOTS_OF_UNITY[log_n]));
+
+            best_ifft(&mut b, Fq::ROOTS_OF_UNITY_INV[log_n], log_n as u32);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_coset_fft() {
+        let mut rng = rng();
+        for log_n in 0..7 {
+            let a: std::vec::Vec<Fp> = (0..1 << log_n).map(|_| Fp::random(&mut rng)).collect();
+
+            let mut b = a.clone();
+            coset_fft(&mut b, Fp::ROOTS_OF_UNITY[log_n], log_n as u32);
+            for (i, b) in b.iter().enumerate() {
+                let x = Fp::ZETA * Fp::ROOTS_OF_UNITY[log_n].pow_vartime(&[i as u64]);
+                let expected = a.iter().rev().fold(Fp::zero(), |acc, c| acc * x + c);
+                assert_eq!(*b, expected);
+            }
+
+            coset_ifft(&mut b, Fp::ROOTS_OF_UNITY_INV[log_n], log_n as u32);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_lde() {
+        let mut rng = rng();
+        let log_n = 4;
+        let coeffs: std::vec::Vec<Fp> = (0..1 << log_n).map(|_| Fp::random(&mut rng)).collect();
+
+        let mut values = coeffs.clone();
+        best_fft(&mut values, Fp::ROOTS_OF_UNITY[log_n], log_n as u32);
+
+        for log_blowup in 0..4 {
+            let extended = lde(&values, 1 << log_blowup);
+            assert_eq!(extended.len(), 1 << (log_n + log_blowup));
+
+            let omega = Fp::ROOTS_OF_UNITY[log_n + log_blowup];
+            for (i, e) in extended.iter().enumerate() {
+                let x = Fp::ZETA * omega.pow_vartime(&[i as u64]);
+                let expected = coeffs.iter().rev().fold(Fp::zero(), |acc, c| acc * x + c);
+                assert_eq!(*e, expected);
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_lde_ep() {
+        let mut rng = rng();
+        let values: std::vec::Vec<Ep> = (0..4).map(|_| Ep::random(&mut rng)).collect();
+
+        let mut extended = lde(&values, 2);
+        coset_ifft(&mut extended, Fq::ROOTS_OF_UNITY_INV[3], 3);
+        best_fft(&mut extended[..4], Fq::ROOTS_OF_UNITY[2], 2);
+        assert_eq!(&extended[..4], &values[..]);
+        assert!(extended[4..].iter().all(|p| bool::from(p.is_identity())));
+    }
+
+    #[test]
+    fn test_bit_reverse_permute() {
+        for log_n in 0..14 {
+            let n = 1usize << log_n;
+            let mut a: std::vec::Vec<usize> = (0..n).collect();
+            bit_reverse_permute(&mut a);
+            for (i, x) in a.iter().enumerate() {
+                let expected = if log_n == 0 {
+                    0
+                } else {
+                    i.reverse_bits() >> (usize::BITS - log_n)
+                };
+                assert_eq!(*x, expected);
+            }
+
+            // The permutation is an involution.
+            bit_reverse_permute(&mut a);
+            assert!(a.iter().enumerate().all(|(i, x)| i == *x));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bit_reverse_permute_wrong_length() {
+        bit_reverse_permute(&mut [0u8; 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fft_wrong_length() {
+        let mut a = [Fp::one(); 3];
+        best_fft(&mut a, Fp::ROOTS_OF_UNITY[2], 2);
+    }
+}
@@ -5,13 +5,19 @@ use core::mem::size_of;
 
 use static_assertions::const_assert;
 
-#[cfg(feature = "sqrt-table")]
-use alloc::{boxed::Box, vec::Vec};
-#[cfg(feature = "sqrt-table")]
+#[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
 use core::marker::PhantomData;
 
-#[cfg(feature = "sqrt-table")]
+#[cfg(feature = "alloc")]
+use ff::Field;
+
 use subtle::Choice;
+#[cfg(any(not(feature = "sqrt-table"), feature = "zkvm"))]
+use subtle::{ConditionallySelectable, ConstantTimeEq, CtOption};
 
 const_assert!(size_of::<usize>() >= 4);
 
@@ -29,7 +35,7 @@ pub(crate) trait SqrtTableHelpers: ff::PrimeField {
 }
 
 /// Parameters for a perfect hash function used in square root computation.
-#[cfg(feature = "sqrt-table")]
+#[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
 #[cfg_attr(docsrs, doc(cfg(feature = "sqrt-table")))]
 #[derive(Debug)]
 struct SqrtHasher<F: SqrtTableHelpers> {
@@ -38,7 +44,7 @@ struct SqrtHasher<F: SqrtTableHelpers> {
     marker: PhantomData<F>,
 }
 
-#[cfg(feature = "sqrt-table")]
+#[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
 impl<F: SqrtTableHelpers> SqrtHasher<F> {
     /// Returns a perfect hash of x for use with SqrtTables::inv.
     fn hash(&self, x: &F) -> usize {
@@ -51,7 +57,7 @@ impl<F: SqrtTableHelpers> SqrtHasher<F> {
 }
 
 /// Tables used for square root computation.
-#[cfg(feature = "sqrt-table")]
+#[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
 #[cfg_attr(docsrs, doc(cfg(feature = "sqrt-table")))]
 #[derive(Debug)]
 pub(crate) struct SqrtTables<F: SqrtTableHelpers> {
@@ -63,7 +69,7 @@ pub(crate) struct SqrtTables<F: SqrtTableHelpers> {
     g3: Box<[F; 129]>,
 }
 
-#[cfg(feature = "sqrt-table")]
+#[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
 impl<F: SqrtTableHelpers> SqrtTables<F> {
     /// Build tables given parameters for the perfect hash.
     pub fn new(hash_xor: u32, hash_mod: usize) -> Self {
@@ -236,7 +242,533 @@ impl<F: SqrtTableHelpers> SqrtTables<F> {
     }
 }
 
+/// Constant-time Tonelli-Shanks square root, used when the `sqrt-table` feature
+/// is disabled or the `zkvm` feature is enabled.
+///
+/// This is the same algorithm as `ff::helpers::sqrt_tonelli_shanks`, except that
+/// $w = u^{(t-1)/2}$ is computed with the field's addition chain rather than a
+/// generic exponentiation.
+#[cfg(any(not(feature = "sqrt-table"), feature = "zkvm"))]
+pub(crate) fn sqrt_tonelli_shanks<F: SqrtTableHelpers>(f: &F) -> CtOption<F> {
+    // w = self^((t - 1) // 2)
+    let w = f.pow_by_t_minus1_over2();
+
+    let mut v = F::S;
+    let mut x = w * f;
+    let mut b = x * w;
+
+    // Initialize z as the 2^S root of unity.
+    let mut z = F::ROOT_OF_UNITY;
+
+    for max_v in (1..=F::S).rev() {
+        let mut k = 1;
+        let mut tmp = b.square();
+        let mut j_less_than_v: Choice = 1.into();
+
+        for j in 2..max_v {
+            let tmp_is_one = tmp.ct_eq(&F::ONE);
+            let squared = F::conditional_select(&tmp, &z, tmp_is_one).square();
+            tmp = F::conditional_select(&squared, &tmp, tmp_is_one);
+            let new_z = F::conditional_select(&z, &squared, tmp_is_one);
+            j_less_than_v &= !j.ct_eq(&v);
+            k = u32::conditional_select(&j, &k, tmp_is_one);
+            z = F::conditional_select(&z, &new_z, j_less_than_v);
+        }
+
+        let result = x * z;
+        x = F::conditional_select(&result, &x, b.ct_eq(&F::ONE));
+        z = z.square();
+        b *= z;
+        v = k;
+    }
+
+    CtOption::new(x, (x * x).ct_eq(f))
+}
+
+/// Inverts every element of `v` in place using Montgomery's trick, so that only
+/// a single field inversion is performed. Elements equal to zero are left
+/// unchanged.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn batch_invert<F: Field>(v: &mut [F]) {
+    let mut prods = Vec::with_capacity(v.len());
+    batch_invert_with_scratch(v, &mut prods);
+}
+
+/// Same as [`batch_invert`], but uses `prods` as scratch space for the running
+/// products so that the caller can clear it afterwards.
+#[cfg(feature = "alloc")]
+pub(crate) fn batch_invert_with_scratch<F: Field>(v: &mut [F], prods: &mut Vec<F>) {
+    // Compute the running products of all nonzero elements.
+    let mut acc = F::ONE;
+    prods.clear();
+    prods.reserve(v.len());
+    for x in v.iter() {
+        prods.push(acc);
+        // We skip all zeroes in v.
+        acc = F::conditional_select(&(acc * x), &acc, x.is_zero());
+    }
+
+    // This is the inverse, as all elements are nonzero and the ones that are
+    // not are skipped.
+    acc = acc.invert().unwrap();
+
+    for (x, prod) in v.iter_mut().rev().zip(prods.iter().rev()) {
+        let skip = x.is_zero();
+
+        // Compute tmp = 1/x
+        let tmp = acc * prod;
+
+        // Cancel out x in the denominator of `acc`
+        acc = F::conditional_select(&(acc * *x), &acc, skip);
+
+        *x = F::conditional_select(&tmp, x, skip);
+    }
+}
+
+/// Computes the inverse of `a` modulo the odd prime `modulus` in variable time,
+/// using the binary extended Euclidean algorithm. Both inputs and the output are
+/// plain (non-Montgomery) little-endian integers; `a` must be less than `modulus`.
+///
+/// Returns `None` if `a` is zero.
+pub(crate) fn invert_vartime_limbs(a: &[u64; 4], modulus: &[u64; 4]) -> Option<[u64; 4]> {
+    const ONE: [u64; 4] = [1, 0, 0, 0];
+
+    fn is_even(x: &[u64; 4]) -> bool {
+        x[0] & 1 == 0
+    }
+
+    fn shr1(x: &mut [u64; 4], top: u64) {
+        x[0] = (x[0] >> 1) | (x[1] << 63);
+        x[1] = (x[1] >> 1) | (x[2] << 63);
+        x[2] = (x[2] >> 1) | (x[3] << 63);
+        x[3] = (x[3] >> 1) | (top << 63);
+    }
+
+    /// Returns x - y and the final borrow.
+    fn sub(x: &[u64; 4], y: &[u64; 4]) -> ([u64; 4], u64) {
+        let (r0, borrow) = sbb(x[0], y[0], 0);
+        let (r1, borrow) = sbb(x[1], y[1], borrow);
+        let (r2, borrow) = sbb(x[2], y[2], borrow);
+        let (r3, borrow) = sbb(x[3], y[3], borrow);
+        ([r0, r1, r2, r3], borrow)
+    }
+
+    /// Halves x modulo the modulus.
+    fn half_mod(x: &mut [u64; 4], modulus: &[u64; 4]) {
+        if is_even(x) {
+            shr1(x, 0);
+        } else {
+            let (r0, carry) = adc(x[0], modulus[0], 0);
+            let (r1, carry) = adc(x[1], modulus[1], carry);
+            let (r2, carry) = adc(x[2], modulus[2], carry);
+            let (r3, carry) = adc(x[3], modulus[3], carry);
+            *x = [r0, r1, r2, r3];
+            shr1(x, carry);
+        }
+    }
+
+    /// Computes x - y modulo the modulus.
+    fn sub_mod(x: &[u64; 4], y: &[u64; 4], modulus: &[u64; 4]) -> [u64; 4] {
+        let (r, borrow) = sub(x, y);
+        if borrow == 0 {
+            return r;
+        }
+        let (r0, carry) = adc(r[0], modulus[0], 0);
+        let (r1, carry) = adc(r[1], modulus[1], carry);
+        let (r2, carry) = adc(r[2], modulus[2], carry);
+        let (r3, _) = adc(r[3], modulus[3], carry);
+        [r0, r1, r2, r3]
+    }
+
+    if *a == [0; 4] {
+        return None;
+    }
+
+    // Invariants: u == x1 * a and v == x2 * a (mod modulus).
+    let mut u = *a;
+    let mut v = *modulus;
+    let mut x1 = ONE;
+    let mut x2 = [0; 4];
+
+    while u != ONE && v != ONE {
+        while is_even(&u) {
+            shr1(&mut u, 0);
+            half_mod(&mut x1, modulus);
+        }
+        while is_even(&v) {
+            shr1(&mut v, 0);
+            half_mod(&mut x2, modulus);
+        }
+
+        let (t, borrow) = sub(&u, &v);
+        if borrow == 0 {
+            u = t;
+            x1 = sub_mod(&x1, &x2, modulus);
+        } else {
+            v = sub(&v, &u).0;
+            x2 = sub_mod(&x2, &x1, modulus);
+        }
+    }
+
+    Some(if u == ONE { x1 } else { x2 })
+}
+
+/// Computes the Montgomery product `a * b * 2^-256 mod modulus` using 32-bit limbs,
+/// so that only 32x32 -> 64-bit multiplications are needed. `inv` is
+/// `-modulus^-1 mod 2^64`.
+///
+/// `b` must be less than `modulus`, which must be less than `2^255`; `a` may be
+/// unreduced. The result is less than `2 * modulus`, and must be reduced by the
+/// caller.
+#[cfg(feature = "limbs-32")]
+pub(crate) const fn mont_mul_32(
+    a: &[u64; 4],
+    b: &[u64; 4],
+    modulus: &[u64; 4],
+    inv: u64,
+) -> [u64; 4] {
+    /// Compute a + (b * c) + carry, returning the result and the new carry over.
+    #[inline(always)]
+    const fn mac32(a: u32, b: u32, c: u32, carry: u32) -> (u32, u32) {
+        let ret = (a as u64) + ((b as u64) * (c as u64)) + (carry as u64);
+        (ret as u32, (ret >> 32) as u32)
+    }
+
+    #[inline(always)]
+    const fn split(x: &[u64; 4]) -> [u32; 8] {
+        [
+            x[0] as u32,
+            (x[0] >> 32) as u32,
+            x[1] as u32,
+            (x[1] >> 32) as u32,
+            x[2] as u32,
+            (x[2] >> 32) as u32,
+            x[3] as u32,
+            (x[3] >> 32) as u32,
+        ]
+    }
+
+    let a = split(a);
+    let b = split(b);
+    let m = split(modulus);
+    let inv = inv as u32;
+
+    // Coarsely Integrated Operand Scanning (CIOS), Algorithm 2 in
+    // "Analyzing and Comparing Montgomery Multiplication Algorithms"
+    // <https://www.microsoft.com/en-us/research/wp-content/uploads/1996/01/j37acmon.pdf>.
+    let mut t = [0u32; 10];
+    let mut i = 0;
+    while i < 8 {
+        let mut carry = 0;
+        let mut j = 0;
+        while j < 8 {
+            let (tj, c) = mac32(t[j], a[j], b[i], carry);
+            t[j] = tj;
+            carry = c;
+            j += 1;
+        }
+        let (t8, c) = mac32(t[8], carry, 1, 0);
+        t[8] = t8;
+        t[9] = c;
+
+        let k = t[0].wrapping_mul(inv);
+        let (_, mut carry) = mac32(t[0], k, m[0], 0);
+        let mut j = 1;
+        while j < 8 {
+            let (tj, c) = mac32(t[j], k, m[j], carry);
+            t[j - 1] = tj;
+            carry = c;
+            j += 1;
+        }
+        let (t7, c) = mac32(t[8], carry, 1, 0);
+        t[7] = t7;
+        t[8] = t[9] + c;
+
+        i += 1;
+    }
+
+    [
+        (t[0] as u64) | ((t[1] as u64) << 32),
+        (t[2] as u64) | ((t[3] as u64) << 32),
+        (t[4] as u64) | ((t[5] as u64) << 32),
+        (t[6] as u64) | ((t[7] as u64) << 32),
+    ]
+}
+
+/// Computes the Montgomery product `a * b * 2^-256 mod modulus` by interleaving
+/// the multiplication and reduction steps, rather than computing the full
+/// 512-bit product first. `inv` is `-modulus^-1 mod 2^64`.
+///
+/// `b` must be less than `modulus`, which must be less than `2^255`; `a` may be
+/// unreduced. The result is less than `2 * modulus`, and must be reduced by the
+/// caller.
+#[cfg(all(feature = "mul-cios", not(feature = "limbs-32")))]
+pub(crate) const fn mont_mul_cios(
+    a: &[u64; 4],
+    b: &[u64; 4],
+    modulus: &[u64; 4],
+    inv: u64,
+) -> [u64; 4] {
+    // Coarsely Integrated Operand Scanning (CIOS), as in `mont_mul_32`.
+    let mut t = [0u64; 6];
+    let mut i = 0;
+    while i < 4 {
+        let mut carry = 0;
+        let mut j = 0;
+        while j < 4 {
+            let (tj, c) = mac(t[j], a[j], b[i], carry);
+            t[j] = tj;
+            carry = c;
+            j += 1;
+        }
+        let (t4, c) = adc(t[4], carry, 0);
+        t[4] = t4;
+        t[5] = c;
+
+        let k = t[0].wrapping_mul(inv);
+        let (_, mut carry) = mac(t[0], k, modulus[0], 0);
+        let mut j = 1;
+        while j < 4 {
+            let (tj, c) = mac(t[j], k, modulus[j], carry);
+            t[j - 1] = tj;
+            carry = c;
+            j += 1;
+        }
+        let (t3, c) = adc(t[4], carry, 0);
+        t[3] = t3;
+        t[4] = t[5] + c;
+
+        i += 1;
+    }
+
+    [t[0], t[1], t[2], t[3]]
+}
+
+/// Computes the Montgomery product `a * b * 2^-256 mod modulus` using the
+/// MULX/ADCX/ADOX instructions. `inv` is `-modulus^-1 mod 2^64`.
+///
+/// The inputs must be less than `modulus`, which must be less than `2^255`. The
+/// result is less than `2 * modulus`, and must be reduced by the caller.
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+#[inline]
+#[allow(unsafe_code)]
+pub(crate) fn mont_mul_asm(a: &[u64; 4], b: &[u64; 4], modulus: &[u64; 4], inv: u64) -> [u64; 4] {
+    use core::arch::asm;
+
+    let r0: u64;
+    let r1: u64;
+    let r2: u64;
+    let r3: u64;
+
+    // This is the same CIOS algorithm as `mont_mul_32`, with the four rounds
+    // unrolled. Each round adds `a * b[i]` and then `k * modulus` to the five-limb
+    // accumulator using two independent carry chains (CF via ADCX for the high
+    // halves, OF via ADOX for the low halves), after which the lowest limb is zero
+    // and is reused as the highest limb of the next round. Since modulus < 2^255,
+    // the accumulator never exceeds five limbs.
+    //
+    // SAFETY: the pointers are derived from references to `[u64; 4]`, and only
+    // offsets 0 through 24 are read.
+    unsafe {
+        asm!(
+            "xor r13d, r13d",
+
+            // Round 0: accumulator is r8, r9, r10, r11, r12.
+            "xor r8d, r8d",
+            "xor r9d, r9d",
+            "xor r10d, r10d",
+            "xor r11d, r11d",
+            "xor r12d, r12d",
+            "mov rdx, qword ptr [{b} + 0]",
+            "mulx rcx, rax, qword ptr [{a} + 0]",
+            "adox r8, rax",
+            "adcx r9, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 8]",
+            "adox r9, rax",
+            "adcx r10, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 16]",
+            "adox r10, rax",
+            "adcx r11, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 24]",
+            "adox r11, rax",
+            "adcx r12, rcx",
+            "adox r12, r13",
+            "mov rdx, r8",
+            "imul rdx, {inv}",
+            "xor eax, eax",
+            "mulx rcx, rax, qword ptr [{m} + 0]",
+            "adox r8, rax",
+            "adcx r9, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 8]",
+            "adox r9, rax",
+            "adcx r10, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 16]",
+            "adox r10, rax",
+            "adcx r11, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 24]",
+            "adox r11, rax",
+            "adcx r12, rcx",
+            "adox r12, r13",
+
+            // Round 1: accumulator is r9, r10, r11, r12, r8.
+            "xor r8d, r8d",
+            "mov rdx, qword ptr [{b} + 8]",
+            "mulx rcx, rax, qword ptr [{a} + 0]",
+            "adox r9, rax",
+            "adcx r10, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 8]",
+            "adox r10, rax",
+            "adcx r11, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 16]",
+            "adox r11, rax",
+            "adcx r12, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 24]",
+            "adox r12, rax",
+            "adcx r8, rcx",
+            "adox r8, r13",
+            "mov rdx, r9",
+            "imul rdx, {inv}",
+            "xor eax, eax",
+            "mulx rcx, rax, qword ptr [{m} + 0]",
+            "adox r9, rax",
+            "adcx r10, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 8]",
+            "adox r10, rax",
+            "adcx r11, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 16]",
+            "adox r11, rax",
+            "adcx r12, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 24]",
+            "adox r12, rax",
+            "adcx r8, rcx",
+            "adox r8, r13",
+
+            // Round 2: accumulator is r10, r11, r12, r8, r9.
+            "xor r9d, r9d",
+            "mov rdx, qword ptr [{b} + 16]",
+            "mulx rcx, rax, qword ptr [{a} + 0]",
+            "adox r10, rax",
+            "adcx r11, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 8]",
+            "adox r11, rax",
+            "adcx r12, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 16]",
+            "adox r12, rax",
+            "adcx r8, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 24]",
+            "adox r8, rax",
+            "adcx r9, rcx",
+            "adox r9, r13",
+            "mov rdx, r10",
+            "imul rdx, {inv}",
+            "xor eax, eax",
+            "mulx rcx, rax, qword ptr [{m} + 0]",
+            "adox r10, rax",
+            "adcx r11, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 8]",
+            "adox r11, rax",
+            "adcx r12, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 16]",
+            "adox r12, rax",
+            "adcx r8, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 24]",
+            "adox r8, rax",
+            "adcx r9, rcx",
+            "adox r9, r13",
+
+            // Round 3: accumulator is r11, r12, r8, r9, r10.
+            "xor r10d, r10d",
+            "mov rdx, qword ptr [{b} + 24]",
+            "mulx rcx, rax, qword ptr [{a} + 0]",
+            "adox r11, rax",
+            "adcx r12, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 8]",
+            "adox r12, rax",
+            "adcx r8, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 16]",
+            "adox r8, rax",
+            "adcx r9, rcx",
+            "mulx rcx, rax, qword ptr [{a} + 24]",
+            "adox r9, rax",
+            "adcx r10, rcx",
+            "adox r10, r13",
+            "mov rdx, r11",
+            "imul rdx, {inv}",
+            "xor eax, eax",
+            "mulx rcx, rax, qword ptr [{m} + 0]",
+            "adox r11, rax",
+            "adcx r12, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 8]",
+            "adox r12, rax",
+            "adcx r8, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 16]",
+            "adox r8, rax",
+            "adcx r9, rcx",
+            "mulx rcx, rax, qword ptr [{m} + 24]",
+            "adox r9, rax",
+            "adcx r10, rcx",
+            "adox r10, r13",
+
+            // The result is r12, r8, r9, r10.
+            a = in(reg) a.as_ptr(),
+            b = in(reg) b.as_ptr(),
+            m = in(reg) modulus.as_ptr(),
+            inv = in(reg) inv,
+            out("rax") _,
+            out("rcx") _,
+            out("rdx") _,
+            out("r8") r1,
+            out("r9") r2,
+            out("r10") r3,
+            out("r11") _,
+            out("r12") r0,
+            out("r13") _,
+            options(pure, readonly, nostack)
+        );
+    }
+
+    [r0, r1, r2, r3]
+}
+
+/// Computes `a * k` modulo `modulus`, up to one extra multiple of `modulus`: the result
+/// is less than `2 * modulus`, and callers reduce it with a final conditional
+/// subtraction. `a` must be less than `modulus`.
+///
+/// This requires `2^254 < modulus < 2^254 + 2^128`, which holds for both Pasta moduli,
+/// so that the quotient `(a * k) / modulus` is approximated by `(a * k) >> 254`.
+#[inline(always)]
+pub(crate) const fn mul_by_u64_limbs(a: &[u64; 4], k: u64, modulus: &[u64; 4]) -> [u64; 4] {
+    // v = a * k < 2^318
+    let (v0, carry) = mac(0, a[0], k, 0);
+    let (v1, carry) = mac(0, a[1], k, carry);
+    let (v2, carry) = mac(0, a[2], k, carry);
+    let (v3, v4) = mac(0, a[3], k, carry);
+
+    // q = v >> 254 < 2^64. Writing modulus = 2^254 + c, we have
+    // v - q * modulus = (v mod 2^254) - q * c, which lies in (-modulus, modulus).
+    let q = (v4 << 2) | (v3 >> 62);
+    let (m0, carry) = mac(0, modulus[0], q, 0);
+    let (m1, carry) = mac(0, modulus[1], q, carry);
+    let (m2, carry) = mac(0, modulus[2], q, carry);
+    let (m3, _) = mac(0, modulus[3], q, carry);
+
+    // w = v - q * modulus + modulus, which lies in [0, 2 * modulus) and so can be
+    // computed modulo 2^256.
+    let (w0, borrow) = sbb(v0, m0, 0);
+    let (w1, borrow) = sbb(v1, m1, borrow);
+    let (w2, borrow) = sbb(v2, m2, borrow);
+    let (w3, _) = sbb(v3, m3, borrow);
+    let (w0, carry) = adc(w0, modulus[0], 0);
+    let (w1, carry) = adc(w1, modulus[1], carry);
+    let (w2, carry) = adc(w2, modulus[2], carry);
+    let (w3, _) = adc(w3, modulus[3], carry);
+
+    [w0, w1, w2, w3]
+}
+
 /// Compute a + b + carry, returning the result and the new carry over.
+#[cfg(not(feature = "limbs-32"))]
 #[inline(always)]
 pub(crate) const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
     let ret = (a as u128) + (b as u128) + (carry as u128);
@@ -244,6 +776,7 @@ pub(crate) const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
 }
 
 /// Compute a - (b + borrow), returning the result and the new borrow.
+#[cfg(not(feature = "limbs-32"))]
 #[inline(always)]
 pub(crate) const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
     let ret = (a as u128).wrapping_sub((b as u128) + ((borrow >> 63) as u128));
@@ -251,8 +784,76 @@ pub(crate) const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
 }
 
 /// Compute a + (b * c) + carry, returning the result and the new carry over.
+#[cfg(not(feature = "limbs-32"))]
 #[inline(always)]
 pub(crate) const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
     let ret = (a as u128) + ((b as u128) * (c as u128)) + (carry as u128);
     (ret as u64, (ret >> 64) as u64)
 }
+
+// With the `limbs-32` feature, the helpers below are computed without `u128`
+// arithmetic, which is slow or unsupported on some 32-bit targets.
+
+/// Compute a + b + carry, returning the result and the new carry over.
+#[cfg(feature = "limbs-32")]
+#[inline(always)]
+pub(crate) const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let (ret, c0) = a.overflowing_add(b);
+    let (ret, c1) = ret.overflowing_add(carry);
+    (ret, c0 as u64 + c1 as u64)
+}
+
+/// Compute a - (b + borrow), returning the result and the new borrow.
+#[cfg(feature = "limbs-32")]
+#[inline(always)]
+pub(crate) const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let (ret, b0) = a.overflowing_sub(b);
+    let (ret, b1) = ret.overflowing_sub(borrow >> 63);
+    (ret, 0u64.wrapping_sub((b0 | b1) as u64))
+}
+
+/// Compute a + (b * c) + carry, returning the result and the new carry over.
+#[cfg(feature = "limbs-32")]
+#[inline(always)]
+pub(crate) const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    // b * c from four 32x32 -> 64-bit products.
+    let (b0, b1) = (b & 0xffff_ffff, b >> 32);
+    let (c0, c1) = (c & 0xffff_ffff, c >> 32);
+    let lo = b0 * c0;
+    let mid0 = b0 * c1;
+    let mid1 = b1 * c0;
+    let mid = (lo >> 32) + (mid0 & 0xffff_ffff) + (mid1 & 0xffff_ffff);
+    let lo = (lo & 0xffff_ffff) | (mid << 32);
+    let hi = b1 * c1 + (mid0 >> 32) + (mid1 >> 32) + (mid >> 32);
+
+    let (ret, c0) = lo.overflowing_add(a);
+    let (ret, c1) = ret.overflowing_add(carry);
+    (ret, hi + c0 as u64 + c1 as u64)
+}
+
+#[cfg(all(test, feature = "limbs-32"))]
+mod tests {
+    use super::{adc, mac, sbb};
+
+    const VALUES: [u64; 6] = [0, 1, 0xffff_ffff, 0x1_0000_0000, u64::MAX - 1, u64::MAX];
+
+    #[test]
+    fn test_helpers_without_u128() {
+        for &a in VALUES.iter() {
+            for &b in VALUES.iter() {
+                for &c in VALUES.iter() {
+                    let ret = (a as u128) + (b as u128) + (c as u128);
+                    assert_eq!(adc(a, b, c), (ret as u64, (ret >> 64) as u64));
+
+                    let ret = (a as u128) + ((b as u128) * (c as u128)) + (b as u128);
+                    assert_eq!(mac(a, b, c, b), (ret as u64, (ret >> 64) as u64));
+                }
+
+                for &borrow in [0, u64::MAX].iter() {
+                    let ret = (a as u128).wrapping_sub((b as u128) + ((borrow >> 63) as u128));
+                    assert_eq!(sbb(a, b, borrow), (ret as u64, (ret >> 64) as u64));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,620 @@
+//! This module provides multi-scalar multiplication over the curves that implement
+//! [`CurveAffine`].
+
+use alloc::vec::Vec;
+
+use ff::PrimeField;
+use group::Group;
+
+#[cfg(feature = "multicore")]
+use rayon::prelude::*;
+
+use super::{Coordinates, CurveAffine};
+use crate::DecodeError;
+
+/// Returns the `c`-bit window at index `segment` of the little-endian encoding `bytes`.
+fn get_window(bytes: &[u8], segment: usize, c: usize) -> usize {
+    let skip_bits = segment * c;
+    let skip_bytes = skip_bits / 8;
+    if skip_bytes >= bytes.len() {
+        return 0;
+    }
+
+    let mut v = [0; 8];
+    for (v, o) in v.iter_mut().zip(bytes[skip_bytes..].iter()) {
+        *v = *o;
+    }
+
+    let tmp = u64::from_le_bytes(v) >> (skip_bits - skip_bytes * 8);
+    (tmp % (1 << c)) as usize
+}
+
+/// Returns the Pippenger window size for `n` terms, which is approximately `ln(n)`.
+fn window_size(n: usize) -> usize {
+    if n < 4 {
+        1
+    } else if n < 32 {
+        3
+    } else {
+        // ln(n) = log2(n) * ln(2), and ln(2) is approximately 69 / 100.
+        let log2 = (usize::BITS - n.leading_zeros() - 1) as usize;
+        (log2 * 69 + 99) / 100
+    }
+}
+
+/// A Pippenger bucket, kept in affine form until a second point is added to it.
+#[derive(Clone, Copy)]
+enum Bucket<C: CurveAffine> {
+    None,
+    Affine(C),
+    Projective(C::Curve),
+}
+
+impl<C: CurveAffine> Bucket<C> {
+    fn add_assign(&mut self, other: &C) {
+        *self = match *self {
+            Bucket::None => Bucket::Affine(*other),
+            Bucket::Affine(a) => Bucket::Projective(a + *other),
+            Bucket::Projective(mut a) => {
+                a += other;
+                Bucket::Projective(a)
+            }
+        }
+    }
+
+    fn add(self, mut other: C::Curve) -> C::Curve {
+        match self {
+            Bucket::None => other,
+            Bucket::Affine(a) => {
+                other += a;
+                other
+            }
+            Bucket::Projective(a) => other + a,
+        }
+    }
+}
+
+/// Adds `base` to the bucket of the signed digit for `window` plus the incoming
+/// `carry`, and sets `carry` to the carry into the next window.
+///
+/// The digit is in `[-2^(c-1), 2^(c-1)]`, and a negative digit adds the negated base,
+/// so there are only `2^(c-1)` buckets.
+fn add_signed_digit<C: CurveAffine>(
+    buckets: &mut [Bucket<C>],
+    base: &C,
+    window: usize,
+    carry: &mut bool,
+    c: usize,
+) {
+    let window = window + *carry as usize;
+    *carry = window > 1 << (c - 1);
+    if *carry {
+        let digit = (1 << c) - window;
+        if digit != 0 {
+            buckets[digit - 1].add_assign(&-*base);
+        }
+    } else if window != 0 {
+        buckets[window - 1].add_assign(base);
+    }
+}
+
+/// Returns `sum_i (i + 1) * buckets[i]`.
+fn sum_buckets<C: CurveAffine>(buckets: &[Bucket<C>]) -> C::Curve {
+    // Summing the buckets from the top as running sums adds the contents of
+    // bucket `i` exactly `i + 1` times.
+    let mut running_sum = C::Curve::identity();
+    let mut sum = C::Curve::identity();
+    for bucket in buckets.iter().rev() {
+        running_sum = bucket.add(running_sum);
+        sum += &running_sum;
+    }
+    sum
+}
+
+/// Returns the number of signed-digit windows of size `c` for scalars of `C`: one
+/// more than the windows of the encoding, for the final carries.
+fn num_segments<C: CurveAffine>(c: usize) -> usize {
+    let num_bits = <C::ScalarExt as PrimeField>::Repr::default().as_ref().len() * 8;
+    (num_bits + c - 1) / c + 1
+}
+
+fn multiexp_serial<C: CurveAffine>(coeffs: &[C::ScalarExt], bases: &[C]) -> C::Curve {
+    let coeffs: Vec<_> = coeffs.iter().map(|a| a.to_repr()).collect();
+    let c = window_size(bases.len());
+    let segments = num_segments::<C>(c);
+
+    // The windows are recoded into signed digits from the bottom up, so that each
+    // carry is known when it is needed.
+    let mut carries: Vec<bool> = alloc::vec![false; coeffs.len()];
+    let mut buckets: Vec<Bucket<C>> = alloc::vec![Bucket::None; 1 << (c - 1)];
+    let mut window_sums = Vec::with_capacity(segments);
+    for segment in 0..segments {
+        for bucket in buckets.iter_mut() {
+            *bucket = Bucket::None;
+        }
+        for ((coeff, base), carry) in coeffs.iter().zip(bases.iter()).zip(carries.iter_mut()) {
+            let window = get_window(coeff.as_ref(), segment, c);
+            add_signed_digit(&mut buckets, base, window, carry, c);
+        }
+        window_sums.push(sum_buckets(&buckets));
+    }
+
+    window_sums
+        .iter()
+        .rev()
+        .fold(C::Curve::identity(), |acc, window_sum| {
+            (0..c).fold(acc, |acc, _| acc.double()) + window_sum
+        })
+}
+
+/// Computes `sum_i coeffs[i] * bases[i]` using Pippenger's bucket method, with a
+/// window size chosen from the number of terms. The windows of the scalars are
+/// recoded into signed digits, since negating a point is nearly free, so each window
+/// only needs half as many buckets.
+///
+/// This is typically used as `best_multiexp::<EpAffine>` (with [`Fq`] scalars) or
+/// `best_multiexp::<EqAffine>` (with [`Fp`] scalars). If the `multicore` feature is
+/// enabled, the terms are split into one chunk per thread, and the partial sums of
+/// the chunks are added together.
+///
+/// This function is variable-time with respect to the scalars, and must not be used
+/// with secret scalars.
+///
+/// # Panics
+///
+/// Panics if `coeffs` and `bases` have different lengths.
+///
+/// [`Fp`]: crate::Fp
+/// [`Fq`]: crate::Fq
+pub fn best_multiexp<C: CurveAffine>(coeffs: &[C::ScalarExt], bases: &[C]) -> C::Curve {
+    assert_eq!(coeffs.len(), bases.len());
+
+    #[cfg(feature = "multicore")]
+    {
+        let num_threads = rayon::current_num_threads();
+        if coeffs.len() > num_threads {
+            let chunk = (coeffs.len() + num_threads - 1) / num_threads;
+            return coeffs
+                .par_chunks(chunk)
+                .zip(bases.par_chunks(chunk))
+                .map(|(coeffs, bases)| multiexp_serial(coeffs, bases))
+                .reduce(C::Curve::identity, |a, b| a + b);
+        }
+    }
+
+    multiexp_serial(coeffs, bases)
+}
+
+/// Computes `sum_i scalar_i * base_i` over the `(scalar_i, base_i)` pairs of `terms`,
+/// reading at most `chunk_size` pairs into memory at a time.
+///
+/// Each chunk is passed to [`best_multiexp`], so this is suitable for inputs that are
+/// too large to hold in memory, such as bases read from disk. Larger chunks allow
+/// larger Pippenger windows, so `chunk_size` should be as large as memory permits.
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is zero.
+pub fn best_multiexp_iter<C, I>(terms: I, chunk_size: usize) -> C::Curve
+where
+    C: CurveAffine,
+    I: IntoIterator<Item = (C::ScalarExt, C)>,
+{
+    assert!(chunk_size > 0);
+
+    let mut coeffs = Vec::with_capacity(chunk_size);
+    let mut bases = Vec::with_capacity(chunk_size);
+    let mut acc = C::Curve::identity();
+    let mut terms = terms.into_iter().peekable();
+    while terms.peek().is_some() {
+        coeffs.clear();
+        bases.clear();
+        for (coeff, base) in terms.by_ref().take(chunk_size) {
+            coeffs.push(coeff);
+            bases.push(base);
+        }
+        acc += best_multiexp(&coeffs, &bases);
+    }
+
+    acc
+}
+
+/// Precomputed tables for multi-scalar multiplications over a fixed set of bases,
+/// such as the generators of a commitment key.
+///
+/// For each base `B` and each window `j`, the table holds `2^(c * j) * B` in affine
+/// form, where `c` is the window size. A query then needs a single set of buckets
+/// for all of the windows, and no doublings, at the cost of storing about
+/// `256 / c` points per base.
+///
+/// The tables can be saved with [`FixedBaseMsm::to_bytes`] and reloaded with
+/// [`FixedBaseMsm::from_bytes`], which is much cheaper than recomputing them.
+#[derive(Clone, Debug)]
+pub struct FixedBaseMsm<C: CurveAffine> {
+    window_size: usize,
+    segments: usize,
+    tables: Vec<C>,
+}
+
+impl<C: CurveAffine> FixedBaseMsm<C> {
+    /// The largest supported window size.
+    pub const MAX_WINDOW_SIZE: usize = 24;
+
+    /// Precomputes the tables for `bases`, with a window size chosen from the number
+    /// of bases.
+    pub fn new(bases: &[C]) -> Self {
+        let terms = bases.len() * num_segments::<C>(window_size(bases.len()));
+        Self::with_window_size(bases, window_size(terms).min(Self::MAX_WINDOW_SIZE))
+    }
+
+    /// Precomputes the tables for `bases`, with windows of `window_size` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is zero or greater than [`Self::MAX_WINDOW_SIZE`].
+    pub fn with_window_size(bases: &[C], window_size: usize) -> Self {
+        assert!((1..=Self::MAX_WINDOW_SIZE).contains(&window_size));
+
+        let segments = num_segments::<C>(window_size);
+        let mut shifted = Vec::with_capacity(bases.len() * segments);
+        for base in bases {
+            let mut base = base.to_curve();
+            for _ in 0..segments {
+                shifted.push(base);
+                for _ in 0..window_size {
+                    base = base.double();
+                }
+            }
+        }
+
+        let mut tables = alloc::vec![C::identity(); shifted.len()];
+        super::batch_normalize(&shifted, &mut tables);
+
+        FixedBaseMsm {
+            window_size,
+            segments,
+            tables,
+        }
+    }
+
+    /// Returns the number of bases.
+    pub fn len(&self) -> usize {
+        self.tables.len() / self.segments
+    }
+
+    /// Returns `true` if there are no bases.
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Returns the window size, in bits.
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    /// Computes `sum_i scalars[i] * bases[i]`.
+    ///
+    /// As with [`best_multiexp`], this is variable-time with respect to the scalars, and
+    /// is parallelized if the `multicore` feature is enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scalars.len()` is not the number of bases.
+    pub fn msm(&self, scalars: &[C::ScalarExt]) -> C::Curve {
+        assert_eq!(scalars.len(), self.len());
+
+        #[cfg(feature = "multicore")]
+        {
+            let num_threads = rayon::current_num_threads();
+            if scalars.len() > num_threads {
+                let chunk = (scalars.len() + num_threads - 1) / num_threads;
+                return scalars
+                    .par_chunks(chunk)
+                    .zip(self.tables.par_chunks(chunk * self.segments))
+                    .map(|(scalars, tables)| self.msm_serial(scalars, tables))
+                    .reduce(C::Curve::identity, |a, b| a + b);
+            }
+        }
+
+        self.msm_serial(scalars, &self.tables)
+    }
+
+    fn msm_serial(&self, scalars: &[C::ScalarExt], tables: &[C]) -> C::Curve {
+        let c = self.window_size;
+        let mut buckets: Vec<Bucket<C>> = alloc::vec![Bucket::None; 1 << (c - 1)];
+        for (scalar, shifted) in scalars.iter().zip(tables.chunks(self.segments)) {
+            let repr = scalar.to_repr();
+            let mut carry = false;
+            for (segment, base) in shifted.iter().enumerate() {
+                let window = get_window(repr.as_ref(), segment, c);
+                add_signed_digit(&mut buckets, base, window, &mut carry, c);
+            }
+        }
+        sum_buckets(&buckets)
+    }
+
+    /// Returns the length of the encoding of a single table entry.
+    fn point_len() -> usize {
+        <C::Base as PrimeField>::Repr::default().as_ref().len() * 2
+    }
+
+    /// Encodes the tables.
+    ///
+    /// The encoding is the window size as a byte, the number of bases as a
+    /// little-endian `u64`, and then the affine coordinates `x || y` of each table
+    /// entry (with the identity encoded as zeroes).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(9 + self.tables.len() * Self::point_len());
+        bytes.push(self.window_size as u8);
+        bytes.extend_from_slice(&(self.len() as u64).to_le_bytes());
+        for point in &self.tables {
+            let coordinates: Option<Coordinates<C>> = point.coordinates().into();
+            match coordinates {
+                Some(coordinates) => {
+                    bytes.extend_from_slice(coordinates.x().to_repr().as_ref());
+                    bytes.extend_from_slice(coordinates.y().to_repr().as_ref());
+                }
+                None => bytes.resize(bytes.len() + Self::point_len(), 0),
+            }
+        }
+        bytes
+    }
+
+    /// Decodes tables that were encoded with [`FixedBaseMsm::to_bytes`].
+    ///
+    /// Every table entry is checked to be on the curve, but not to be the correct
+    /// multiple of its base, so the encoding must come from a trusted source.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Self::decode(bytes, true)
+    }
+
+    /// Decodes tables that were encoded with [`FixedBaseMsm::to_bytes`], without
+    /// checking that the table entries are on the curve.
+    ///
+    /// This is faster than [`FixedBaseMsm::from_bytes`] for large tables. The
+    /// encoding must come from a trusted source (such as a file written by
+    /// [`FixedBaseMsm::to_bytes`]), as invalid entries give incorrect results.
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Self::decode(bytes, false)
+    }
+
+    fn decode(bytes: &[u8], check: bool) -> Result<Self, DecodeError> {
+        if bytes.len() < 9 {
+            return Err(DecodeError::InvalidLength {
+                expected: 9,
+                actual: bytes.len(),
+            });
+        }
+        let window_size = bytes[0] as usize;
+        if !(1..=Self::MAX_WINDOW_SIZE).contains(&window_size) {
+            return Err(DecodeError::InvalidHeader);
+        }
+        let segments = num_segments::<C>(window_size);
+        let expected = u64::from_le_bytes(bytes[1..9].try_into().unwrap())
+            .checked_mul((segments * Self::point_len()) as u64)
+            .and_then(|len| usize::try_from(len).ok())
+            .and_then(|len| len.checked_add(9))
+            .ok_or(DecodeError::InvalidHeader)?;
+        if bytes.len() != expected {
+            return Err(DecodeError::InvalidLength {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let field_len = Self::point_len() / 2;
+        let decode_field = |bytes: &[u8]| {
+            let mut repr = <C::Base as PrimeField>::Repr::default();
+            repr.as_mut().copy_from_slice(bytes);
+            Option::<C::Base>::from(C::Base::from_repr(repr)).ok_or(DecodeError::NonCanonical)
+        };
+        let tables = bytes[9..]
+            .chunks(Self::point_len())
+            .map(|point| {
+                if point.iter().all(|b| *b == 0) {
+                    return Ok(C::identity());
+                }
+                let x = decode_field(&point[..field_len])?;
+                let y = decode_field(&point[field_len..])?;
+                if check {
+                    Option::from(C::from_xy(x, y)).ok_or(DecodeError::NotOnCurve)
+                } else {
+                    Ok(C::from_xy_unchecked(x, y))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(FixedBaseMsm {
+            window_size,
+            segments,
+            tables,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ep, EpAffine, Eq, EqAffine, Fp, Fq};
+    use ff::Field;
+    use group::{prime::PrimeCurveAffine, Curve};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn naive_multiexp<C: CurveAffine>(coeffs: &[C::ScalarExt], bases: &[C]) -> C::Curve {
+        coeffs
+            .iter()
+            .zip(bases.iter())
+            .fold(C::Curve::identity(), |acc, (coeff, base)| {
+                acc + *base * *coeff
+            })
+    }
+
+    #[test]
+    fn test_window_size() {
+        assert_eq!(window_size(0), 1);
+        assert_eq!(window_size(31), 3);
+        assert_eq!(window_size(1 << 10), 7);
+        assert_eq!(window_size(1 << 20), 14);
+    }
+
+    #[test]
+    fn test_best_multiexp() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for n in [0, 1, 2, 5, 33, 100, 300] {
+            let coeffs: std::vec::Vec<Fq> = (0..n).map(|_| Fq::random(&mut rng)).collect();
+            let bases: std::vec::Vec<EpAffine> =
+                (0..n).map(|_| Ep::random(&mut rng).to_affine()).collect();
+            assert_eq!(
+                best_multiexp(&coeffs, &bases),
+                naive_multiexp(&coeffs, &bases)
+            );
+
+            let coeffs: std::vec::Vec<Fp> = (0..n).map(|_| Fp::random(&mut rng)).collect();
+            let bases: std::vec::Vec<EqAffine> =
+                (0..n).map(|_| Eq::random(&mut rng).to_affine()).collect();
+            assert_eq!(
+                best_multiexp(&coeffs, &bases),
+                naive_multiexp(&coeffs, &bases)
+            );
+        }
+
+        // Edge cases: zero and -1 scalars, and repeated and identity bases.
+        let g = EpAffine::generator();
+        let coeffs = [Fq::zero(), -Fq::one(), Fq::one(), Fq::from(7)];
+        let bases = [g, g, EpAffine::identity(), g];
+        assert_eq!(
+            best_multiexp(&coeffs, &bases),
+            Ep::generator() * Fq::from(6)
+        );
+    }
+
+    #[test]
+    fn test_best_multiexp_iter() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let n = 100;
+        let coeffs: std::vec::Vec<Fq> = (0..n).map(|_| Fq::random(&mut rng)).collect();
+        let bases: std::vec::Vec<EpAffine> =
+            (0..n).map(|_| Ep::random(&mut rng).to_affine()).collect();
+        let expected = best_multiexp(&coeffs, &bases);
+
+        for chunk_size in [1, 7, 50, 100, 1000] {
+            let terms = coeffs.iter().copied().zip(bases.iter().copied());
+            assert_eq!(best_multiexp_iter(terms, chunk_size), expected);
+        }
+        assert_eq!(
+            best_multiexp_iter::<EpAffine, _>(core::iter::empty(), 10),
+            Ep::identity()
+        );
+    }
+
+    #[test]
+    fn test_fixed_base_msm() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for n in [0, 1, 5, 100] {
+            let mut bases: std::vec::Vec<EqAffine> =
+                (0..n).map(|_| Eq::random(&mut rng).to_affine()).collect();
+            if n > 1 {
+                bases[1] = EqAffine::identity();
+            }
+            let msm = FixedBaseMsm::new(&bases);
+            assert_eq!(msm.len(), n);
+
+            let decoded = FixedBaseMsm::<EqAffine>::from_bytes(&msm.to_bytes()).unwrap();
+            assert_eq!(decoded.window_size(), msm.window_size());
+            assert_eq!(decoded.tables, msm.tables);
+
+            for _ in 0..3 {
+                let scalars: std::vec::Vec<Fp> = (0..n).map(|_| Fp::random(&mut rng)).collect();
+                let expected = best_multiexp(&scalars, &bases);
+                assert_eq!(msm.msm(&scalars), expected);
+                assert_eq!(decoded.msm(&scalars), expected);
+            }
+        }
+
+        let bases = [EpAffine::generator()];
+        let scalars = [-Fq::one()];
+        for c in 1..=8 {
+            let msm = FixedBaseMsm::with_window_size(&bases, c);
+            assert_eq!(msm.msm(&scalars), -Ep::generator());
+        }
+    }
+
+    #[test]
+    fn test_fixed_base_msm_from_bytes() {
+        let msm = FixedBaseMsm::new(&[EpAffine::generator(); 2]);
+        let bytes = msm.to_bytes();
+        assert!(FixedBaseMsm::<EpAffine>::from_bytes(&bytes).is_ok());
+        let unchecked = FixedBaseMsm::<EpAffine>::from_bytes_unchecked(&bytes).unwrap();
+        assert_eq!(unchecked.tables, msm.tables);
+
+        assert_eq!(
+            FixedBaseMsm::<EpAffine>::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            DecodeError::InvalidLength {
+                expected: bytes.len(),
+                actual: bytes.len() - 1,
+            }
+        );
+
+        let mut invalid = bytes.clone();
+        invalid[0] = 0;
+        assert_eq!(
+            FixedBaseMsm::<EpAffine>::from_bytes(&invalid).unwrap_err(),
+            DecodeError::InvalidHeader
+        );
+
+        let mut invalid = bytes.clone();
+        invalid[1..9].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(
+            FixedBaseMsm::<EpAffine>::from_bytes(&invalid).unwrap_err(),
+            DecodeError::InvalidHeader
+        );
+
+        // Changing the y-coordinate of the first entry.
+        let mut invalid = bytes.clone();
+        invalid[9 + 32] ^= 1;
+        assert_eq!(
+            FixedBaseMsm::<EpAffine>::from_bytes(&invalid).unwrap_err(),
+            DecodeError::NotOnCurve
+        );
+        assert!(FixedBaseMsm::<EpAffine>::from_bytes_unchecked(&invalid).is_ok());
+
+        let mut invalid = bytes;
+        invalid[9..9 + 32].fill(0xff);
+        assert_eq!(
+            FixedBaseMsm::<EpAffine>::from_bytes(&invalid).unwrap_err(),
+            DecodeError::NonCanonical
+        );
+    }
+
+    #[test]
+    fn test_best_multiexp_large() {
+        // Large enough to be split into chunks for every thread when the `multicore`
+        // feature is enabled.
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let n = 1 << 12;
+        let coeffs: std::vec::Vec<Fq> = (0..n).map(|_| Fq::random(&mut rng)).collect();
+        let bases: std::vec::Vec<EpAffine> =
+            (0..n).map(|_| Ep::random(&mut rng).to_affine()).collect();
+        assert_eq!(
+            best_multiexp(&coeffs, &bases),
+            naive_multiexp(&coeffs, &bases)
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! This module provides recodings of scalars into bits and digits, as used by scalar
+//! multiplication algorithms and circuit decompositions.
+//!
+//! The bits of a scalar can also be obtained directly with
+//! [`PrimeFieldBits::to_le_bits`].
+
+use alloc::vec::Vec;
+
+use ff::PrimeFieldBits;
+use group::Group;
+
+/// Returns the `F::NUM_BITS` bits of the canonical integer value of `scalar`, in
+/// little-endian order.
+pub fn to_le_bits<F: PrimeFieldBits>(scalar: &F) -> Vec<bool> {
+    scalar
+        .to_le_bits()
+        .iter()
+        .by_vals()
+        .take(F::NUM_BITS as usize)
+        .collect()
+}
+
+/// Returns the little-endian base-`2^w` digits of `scalar`.
+///
+/// Each digit `d_i` is in `[0, 2^w)`, and `scalar = sum_i d_i * 2^(w * i)`. There are
+/// `ceil(F::NUM_BITS / w)` digits.
+///
+/// # Panics
+///
+/// Panics if `w` is zero or greater than 64.
+pub fn to_base_w_digits<F: PrimeFieldBits>(scalar: &F, w: usize) -> Vec<u64> {
+    assert!((1..=64).contains(&w));
+
+    to_le_bits(scalar)
+        .chunks(w)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .rev()
+                .fold(0, |acc, bit| (acc << 1) | (*bit as u64))
+        })
+        .collect()
+}
+
+/// Returns the width-`window` non-adjacent form (wNAF) of `scalar`.
+///
+/// Each digit `d_i` is either zero or odd with `|d_i| < 2^(window - 1)`, at most one of
+/// any `window` consecutive digits is non-zero, and `scalar = sum_i d_i * 2^i`. There
+/// are `F::NUM_BITS + 1` digits.
+///
+/// # Panics
+///
+/// Panics if `window` is less than 2 or greater than 32.
+pub fn to_signed_digits<F: PrimeFieldBits>(scalar: &F, window: usize) -> Vec<i64> {
+    assert!((2..=32).contains(&window));
+
+    let bits = to_le_bits(scalar);
+    let n = bits.len();
+    let bit = |i: usize| i < n && bits[i];
+
+    let width = 1i64 << window;
+    let mut digits = alloc::vec![0; n + 1];
+    let mut pos = 0;
+    let mut carry = 0;
+    while pos <= n {
+        // The value of the next `window` bits, plus the carry from the previous digit.
+        let value = (0..window).fold(carry, |acc, j| acc + ((bit(pos + j) as i64) << j));
+
+        if value & 1 == 0 {
+            pos += 1;
+            continue;
+        }
+
+        if value < width / 2 {
+            carry = 0;
+            digits[pos] = value;
+        } else {
+            carry = 1;
+            digits[pos] = value - width;
+        }
+
+        pos += window;
+    }
+    debug_assert_eq!(carry, 0);
+
+    digits
+}
+
+/// Computes `point * scalar` from the width-`window` NAF of `scalar`.
+///
+/// This precomputes the `2^(window - 2)` odd multiples of `point` below
+/// `2^(window - 1)`, and then needs one doubling per bit of `scalar` and on average
+/// one addition per `window + 1` bits. Windows of 4 or 5 are usually fastest.
+///
+/// This is variable-time with respect to `scalar`, so it must only be used with public
+/// scalars, such as in verifiers. The `Mul` impls of the curves in this crate are
+/// constant-time.
+///
+/// # Panics
+///
+/// Panics if `window` is less than 2 or greater than 16.
+pub fn mul_wnaf<G>(point: &G, scalar: &G::Scalar, window: usize) -> G
+where
+    G: Group,
+    G::Scalar: PrimeFieldBits,
+{
+    assert!((2..=16).contains(&window));
+
+    let double = point.double();
+    let mut table = Vec::with_capacity(1 << (window - 2));
+    let mut multiple = *point;
+    table.push(multiple);
+    for _ in 1..1 << (window - 2) {
+        multiple += double;
+        table.push(multiple);
+    }
+
+    to_signed_digits(scalar, window)
+        .iter()
+        .rev()
+        .fold(G::identity(), |acc, d| {
+            let acc = acc.double();
+            match d.signum() {
+                1 => acc + table[(d / 2) as usize],
+                -1 => acc - table[(-d / 2) as usize],
+                _ => acc,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ep, Fq};
+    use ff::{Field, PrimeField};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn scalars() -> impl Iterator<Item = Fq> {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        (0..20).map(move |_| Fq::random(&mut rng)).chain([
+            Fq::zero(),
+            Fq::one(),
+            -Fq::one(),
+            Fq::from(7),
+        ])
+    }
+
+    #[test]
+    fn test_to_le_bits() {
+        for a in scalars() {
+            let bits = to_le_bits(&a);
+            assert_eq!(bits.len(), Fq::NUM_BITS as usize);
+
+            let b = bits
+                .iter()
+                .rev()
+                .fold(Fq::zero(), |acc, bit| acc.double() + Fq::from(*bit as u64));
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_to_base_w_digits() {
+        for w in [1, 2, 3, 4, 5, 8, 13, 32, 64] {
+            for a in scalars() {
+                let digits = to_base_w_digits(&a, w);
+                assert_eq!(digits.len(), (Fq::NUM_BITS as usize + w - 1) / w);
+
+                let base = Fq::from(2).pow_vartime(&[w as u64]);
+                let b = digits.iter().rev().fold(Fq::zero(), |acc, d| {
+                    assert!(w == 64 || *d < 1 << w);
+                    acc * base + Fq::from(*d)
+                });
+                assert_eq!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_signed_digits() {
+        for window in [2, 3, 4, 5, 8, 16, 32] {
+            for a in scalars() {
+                let digits = to_signed_digits(&a, window);
+                assert_eq!(digits.len(), Fq::NUM_BITS as usize + 1);
+
+                let mut last_nonzero = None;
+                for (i, d) in digits.iter().enumerate() {
+                    if *d != 0 {
+                        assert_eq!(d & 1, 1);
+                        assert!(d.abs() < 1 << (window - 1));
+                        if let Some(j) = last_nonzero {
+                            assert!(i - j >= window);
+                        }
+                        last_nonzero = Some(i);
+                    }
+                }
+
+                let b = digits.iter().rev().fold(Fq::zero(), |acc, d| {
+                    let d = if *d < 0 {
+                        -Fq::from(d.unsigned_abs())
+                    } else {
+                        Fq::from(*d as u64)
+                    };
+                    acc.double() + d
+                });
+                assert_eq!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_wnaf() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for window in [2, 3, 4, 5, 8] {
+            for a in scalars() {
+                let p = Ep::random(&mut rng);
+                assert_eq!(mul_wnaf(&p, &a, window), p * a);
+            }
+            let a = Fq::random(&mut rng);
+            assert_eq!(mul_wnaf(&Ep::identity(), &a, window), Ep::identity());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_to_signed_digits_window_too_small() {
+        to_signed_digits(&Fq::one(), 1);
+    }
+}
@@ -0,0 +1,191 @@
+//! Conversions to and from the [arkworks](https://arkworks.rs) Pallas and Vesta types.
+//!
+//! Note that arkworks names fields relative to the curve: `ark_pallas::Fq` is the Pallas
+//! base field (our [`Fp`]) and `ark_pallas::Fr` is the Pallas scalar field (our [`Fq`]).
+//! `ark_vesta` re-exports the same two types with the names swapped.
+//!
+//! Both crates store field elements in Montgomery form with `R = 2^256`, so the internal
+//! limbs coincide and field conversions are a plain copy with no reduction.
+//!
+//! Arkworks points can be constructed without checking that they are on the curve, so
+//! conversions from them are `TryFrom` impls that fail with
+//! [`DecodeError::NotOnCurve`].
+
+use ark_ec::{short_weierstrass::Affine, AffineRepr, CurveGroup};
+use ark_ff::BigInt;
+use group::{prime::PrimeCurveAffine, Curve};
+
+use crate::{
+    arithmetic::{Coordinates, CurveAffine},
+    curves::{Ep, EpAffine, Eq, EqAffine},
+    fields::{Fp, Fq},
+    DecodeError,
+};
+
+macro_rules! impl_field_conversions {
+    ($field:ident, $ark:ty) => {
+        impl From<$field> for $ark {
+            fn from(a: $field) -> $ark {
+                <$ark>::new_unchecked(BigInt::new(a.0))
+            }
+        }
+
+        impl From<$ark> for $field {
+            fn from(a: $ark) -> $field {
+                $field(a.0 .0)
+            }
+        }
+    };
+}
+
+impl_field_conversions!(Fp, ark_pallas::Fq);
+impl_field_conversions!(Fq, ark_pallas::Fr);
+
+macro_rules! impl_point_conversions {
+    ($name:ident, $name_affine:ident, $ark:ident) => {
+        impl From<$name_affine> for $ark::Affine {
+            fn from(p: $name_affine) -> $ark::Affine {
+                let coordinates: Option<Coordinates<$name_affine>> = p.coordinates().into();
+                coordinates
+                    .map(|c| Affine::new_unchecked((*c.x()).into(), (*c.y()).into()))
+                    .unwrap_or_else(Affine::identity)
+            }
+        }
+
+        impl TryFrom<$ark::Affine> for $name_affine {
+            type Error = DecodeError;
+
+            /// Fails if the point is not on the curve, which can only happen if it was
+            /// constructed with `Affine::new_unchecked`.
+            fn try_from(p: $ark::Affine) -> Result<$name_affine, DecodeError> {
+                match p.xy() {
+                    Some((x, y)) => {
+                        let p = $name_affine::from_xy_unchecked((*x).into(), (*y).into());
+                        p.validate().map(|()| p)
+                    }
+                    None => Ok($name_affine::identity()),
+                }
+            }
+        }
+
+        impl From<$name> for $ark::Projective {
+            fn from(p: $name) -> $ark::Projective {
+                $ark::Affine::from(p.to_affine()).into()
+            }
+        }
+
+        impl TryFrom<$ark::Projective> for $name {
+            type Error = DecodeError;
+
+            /// Fails if the point is not on the curve.
+            fn try_from(p: $ark::Projective) -> Result<$name, DecodeError> {
+                $name_affine::try_from(p.into_affine()).map($name::from)
+            }
+        }
+    };
+}
+
+impl_point_conversions!(Ep, EpAffine, ark_pallas);
+impl_point_conversions!(Eq, EqAffine, ark_vesta);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ark_ff::PrimeField as _;
+    use ff::{Field, PrimeField};
+    use group::Group;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn limbs(repr: [u8; 32]) -> [u64; 4] {
+        let mut limbs = [0; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(repr.chunks(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        limbs
+    }
+
+    #[test]
+    fn arkworks_fields() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        assert_eq!(
+            ark_pallas::Fq::from(Fp::from(5)),
+            ark_pallas::Fq::from(5u64)
+        );
+        assert_eq!(ark_vesta::Fq::from(Fq::from(5)), ark_vesta::Fq::from(5u64));
+
+        for _ in 0..100 {
+            let a = Fp::random(&mut rng);
+            let b = Fp::random(&mut rng);
+            let ark_a = ark_pallas::Fq::from(a);
+            assert_eq!(ark_a.into_bigint().0, limbs(a.to_repr()));
+            assert_eq!(ark_a * ark_pallas::Fq::from(b), (a * b).into());
+            assert_eq!(Fp::from(ark_a), a);
+            assert_eq!(Fp::from(ark_vesta::Fr::from(a)), a);
+
+            let a = Fq::random(&mut rng);
+            let ark_a = ark_vesta::Fq::from(a);
+            assert_eq!(ark_a.into_bigint().0, limbs(a.to_repr()));
+            assert_eq!(Fq::from(ark_a), a);
+            assert_eq!(Fq::from(ark_pallas::Fr::from(a)), a);
+        }
+    }
+
+    #[test]
+    fn arkworks_points() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for _ in 0..100 {
+            let p = Ep::random(&mut rng);
+            assert_eq!(Ep::try_from(ark_pallas::Projective::from(p)), Ok(p));
+            let p = p.to_affine();
+            let ark_p = ark_pallas::Affine::from(p);
+            assert_eq!(
+                ark_p.xy().map(|(x, _)| Fp::from(*x)),
+                Some(*p.coordinates().unwrap().x())
+            );
+            assert_eq!(EpAffine::try_from(ark_p), Ok(p));
+
+            let q = Eq::random(&mut rng);
+            assert_eq!(Eq::try_from(ark_vesta::Projective::from(q)), Ok(q));
+            assert_eq!(
+                EqAffine::try_from(ark_vesta::Affine::from(q.to_affine())),
+                Ok(q.to_affine())
+            );
+        }
+
+        assert_eq!(
+            ark_pallas::Affine::from(EpAffine::identity()),
+            ark_pallas::Affine::zero()
+        );
+        assert_eq!(
+            EpAffine::try_from(ark_pallas::Affine::zero()),
+            Ok(EpAffine::identity())
+        );
+        assert_eq!(
+            ark_vesta::Affine::from(EqAffine::identity()),
+            ark_vesta::Affine::zero()
+        );
+        assert_eq!(
+            EqAffine::try_from(ark_vesta::Affine::zero()),
+            Ok(EqAffine::identity())
+        );
+
+        let off_curve = ark_pallas::Affine::new_unchecked(1u64.into(), 1u64.into());
+        assert_eq!(EpAffine::try_from(off_curve), Err(DecodeError::NotOnCurve));
+        assert_eq!(
+            Ep::try_from(ark_pallas::Projective::from(off_curve)),
+            Err(DecodeError::NotOnCurve)
+        );
+        let off_curve = ark_vesta::Affine::new_unchecked(1u64.into(), 1u64.into());
+        assert_eq!(EqAffine::try_from(off_curve), Err(DecodeError::NotOnCurve));
+    }
+}
@@ -0,0 +1,175 @@
+//! Hooks for delegating arithmetic to an external implementation.
+//!
+//! When this crate is compiled for a zkVM guest, field multiplication and point
+//! addition can be much cheaper as host precompiles or syscalls than as
+//! instructions executed in the guest. With the `custom-backend` feature flag
+//! enabled, these operations are routed through the [`Backend`] installed with
+//! [`set_backend`]:
+//!
+//! ```ignore
+//! use pasta_curves::{backend::{self, Backend}, Fp};
+//!
+//! struct Precompiles;
+//!
+//! impl Backend for Precompiles {
+//!     fn fp_mul(&self, a: &Fp, b: &Fp) -> Option<Fp> {
+//!         Some(my_zkvm::syscall_pallas_fp_mul(a, b))
+//!     }
+//! }
+//!
+//! static PRECOMPILES: &dyn Backend = &Precompiles;
+//!
+//! fn main() {
+//!     backend::set_backend(&PRECOMPILES);
+//!     // ...
+//! }
+//! ```
+//!
+//! Every method returns `None` by default, in which case the built-in
+//! implementation is used, as it is when no backend has been installed.
+//!
+//! Only the operator impls (`Mul` for fields, `Add` of projective points) are
+//! routed through the backend; the `const fn` methods such as [`Fp::mul`] always
+//! use the built-in implementation.
+
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::{
+    curves::{Ep, Eq},
+    fields::{Fp, Fq},
+};
+
+/// An external implementation of some of the arithmetic in this crate.
+///
+/// Each method may return `None` to fall back to the built-in implementation.
+pub trait Backend: Sync {
+    /// Computes `a * b` in the Pallas base field.
+    fn fp_mul(&self, _a: &Fp, _b: &Fp) -> Option<Fp> {
+        None
+    }
+
+    /// Computes `a * b` in the Vesta base field.
+    fn fq_mul(&self, _a: &Fq, _b: &Fq) -> Option<Fq> {
+        None
+    }
+
+    /// Computes `a + b` on the Pallas curve.
+    fn ep_add(&self, _a: &Ep, _b: &Ep) -> Option<Ep> {
+        None
+    }
+
+    /// Computes `a + b` on the Vesta curve.
+    fn eq_add(&self, _a: &Eq, _b: &Eq) -> Option<Eq> {
+        None
+    }
+}
+
+static BACKEND: AtomicPtr<&'static dyn Backend> = AtomicPtr::new(ptr::null_mut());
+
+/// Installs `backend`, replacing any previously installed backend.
+///
+/// This only requires atomic loads and stores, so it is available on targets
+/// without atomic read-modify-write instructions (such as RV32IM).
+pub fn set_backend(backend: &'static &'static dyn Backend) {
+    BACKEND.store(backend as *const _ as *mut _, Ordering::Release);
+}
+
+#[allow(unsafe_code)]
+#[inline]
+fn backend() -> Option<&'static dyn Backend> {
+    let backend = BACKEND.load(Ordering::Acquire);
+    // Only `set_backend` stores to `BACKEND`, from a `&'static` reference.
+    unsafe { backend.as_ref() }.copied()
+}
+
+#[inline]
+pub(crate) fn fp_mul(a: &Fp, b: &Fp) -> Option<Fp> {
+    backend().and_then(|backend| backend.fp_mul(a, b))
+}
+
+#[inline]
+pub(crate) fn fq_mul(a: &Fq, b: &Fq) -> Option<Fq> {
+    backend().and_then(|backend| backend.fq_mul(a, b))
+}
+
+/// Point addition hooks, looked up by curve so that the shared curve macro can
+/// use them. The isogenous curves are never delegated.
+pub(crate) trait AddHook: Sized {
+    fn add_hook(_a: &Self, _b: &Self) -> Option<Self> {
+        None
+    }
+}
+
+impl AddHook for Ep {
+    #[inline]
+    fn add_hook(a: &Ep, b: &Ep) -> Option<Ep> {
+        backend().and_then(|backend| backend.ep_add(a, b))
+    }
+}
+
+impl AddHook for Eq {
+    #[inline]
+    fn add_hook(a: &Eq, b: &Eq) -> Option<Eq> {
+        backend().and_then(|backend| backend.eq_add(a, b))
+    }
+}
+
+impl AddHook for crate::curves::IsoEp {}
+impl AddHook for crate::curves::IsoEq {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::sync::atomic::AtomicUsize;
+
+    use ff::Field;
+    use group::Group;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    static FP_MULS: AtomicUsize = AtomicUsize::new(0);
+    static EP_ADDS: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountingBackend;
+
+    impl Backend for CountingBackend {
+        fn fp_mul(&self, a: &Fp, b: &Fp) -> Option<Fp> {
+            FP_MULS.fetch_add(1, Ordering::Relaxed);
+            Some(a.mul(b))
+        }
+
+        fn ep_add(&self, _: &Ep, _: &Ep) -> Option<Ep> {
+            EP_ADDS.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+
+    static COUNTING_BACKEND: &dyn Backend = &CountingBackend;
+
+    #[test]
+    fn backend_is_used() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // Other tests may run while the backend is installed, so it must give
+        // correct results.
+        set_backend(&COUNTING_BACKEND);
+
+        let a = Fp::random(&mut rng);
+        let b = Fp::random(&mut rng);
+        let muls = FP_MULS.load(Ordering::Relaxed);
+        assert_eq!(a * b, (&a).mul(&b));
+        assert!(FP_MULS.load(Ordering::Relaxed) > muls);
+
+        // Falling back to the built-in implementation.
+        let p = Ep::random(&mut rng);
+        let q = Ep::random(&mut rng);
+        let adds = EP_ADDS.load(Ordering::Relaxed);
+        assert_eq!(p + q - q, p);
+        assert!(EP_ADDS.load(Ordering::Relaxed) > adds);
+    }
+}
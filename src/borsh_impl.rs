@@ -0,0 +1,178 @@
+use borsh::{
+    io::{Error, ErrorKind, Read, Result, Write},
+    BorshDeserialize, BorshSerialize,
+};
+use ff::PrimeField;
+use group::GroupEncoding;
+
+use crate::{
+    curves::{Ep, EpAffine, Eq, EqAffine},
+    fields::{Fp, Fq},
+    group::Curve,
+};
+
+impl BorshSerialize for Fp {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.to_repr().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for Fp {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = <[u8; 32]>::deserialize_reader(reader)?;
+        Option::from(Fp::from_repr(bytes)).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "deserialized bytes don't encode a Pallas field element",
+            )
+        })
+    }
+}
+
+impl BorshSerialize for Fq {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.to_repr().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for Fq {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = <[u8; 32]>::deserialize_reader(reader)?;
+        Option::from(Fq::from_repr(bytes)).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "deserialized bytes don't encode a Vesta field element",
+            )
+        })
+    }
+}
+
+impl BorshSerialize for EpAffine {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.to_bytes().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for EpAffine {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = <[u8; 32]>::deserialize_reader(reader)?;
+        Option::from(EpAffine::from_bytes(&bytes)).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "deserialized bytes don't encode a Pallas curve point",
+            )
+        })
+    }
+}
+
+impl BorshSerialize for EqAffine {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.to_bytes().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for EqAffine {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = <[u8; 32]>::deserialize_reader(reader)?;
+        Option::from(EqAffine::from_bytes(&bytes)).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "deserialized bytes don't encode a Vesta curve point",
+            )
+        })
+    }
+}
+
+impl BorshSerialize for Ep {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.to_affine().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for Ep {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(Self::from(EpAffine::deserialize_reader(reader)?))
+    }
+}
+
+impl BorshSerialize for Eq {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.to_affine().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for Eq {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(Self::from(EqAffine::deserialize_reader(reader)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::fmt::Debug;
+
+    use ff::Field;
+    use group::{prime::PrimeCurveAffine, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn test_roundtrip<T: BorshSerialize + BorshDeserialize + Debug + PartialEq>(t: &T) {
+        let serialized = borsh::to_vec(t).unwrap();
+        assert_eq!(serialized.len(), 32);
+        assert_eq!(*t, borsh::from_slice(&serialized).unwrap());
+    }
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn borsh_fields() {
+        let mut rng = rng();
+        for _ in 0..100 {
+            test_roundtrip(&Fp::random(&mut rng));
+            test_roundtrip(&Fq::random(&mut rng));
+        }
+
+        assert_eq!(borsh::to_vec(&Fp::one()).unwrap(), Fp::one().to_repr());
+        assert_eq!(borsh::to_vec(&Fq::one()).unwrap(), Fq::one().to_repr());
+
+        // Non-canonical encodings are rejected.
+        let err = borsh::from_slice::<Fp>(&[0xff; 32]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let err = borsh::from_slice::<Fq>(&[0xff; 32]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        // So are truncated encodings.
+        assert!(borsh::from_slice::<Fp>(&[0; 31]).is_err());
+    }
+
+    #[test]
+    fn borsh_points() {
+        let mut rng = rng();
+        for _ in 0..100 {
+            let p = Ep::random(&mut rng);
+            test_roundtrip(&p);
+            test_roundtrip(&p.to_affine());
+            let q = Eq::random(&mut rng);
+            test_roundtrip(&q);
+            test_roundtrip(&q.to_affine());
+        }
+
+        test_roundtrip(&Ep::identity());
+        test_roundtrip(&EpAffine::identity());
+        test_roundtrip(&Eq::identity());
+        test_roundtrip(&EqAffine::identity());
+
+        // x = 0 with the sign bit set is not a valid encoding.
+        let mut bytes = [0; 32];
+        bytes[31] = 0x80;
+        assert!(borsh::from_slice::<EpAffine>(&bytes).is_err());
+        assert!(borsh::from_slice::<EqAffine>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use crypto_bigint::{Encoding, U256};
+use ff::PrimeField;
+use subtle::CtOption;
+
+use crate::fields::{Fp, Fq};
+
+macro_rules! impl_uint_conversions {
+    ($field:ident, $modulus:expr) => {
+        impl $field {
+            /// The field modulus as a [`U256`].
+            pub const MODULUS_UINT: U256 = U256::from_be_hex($modulus);
+
+            /// Attempts to convert a [`U256`] into a field element, failing if it is not
+            /// less than the field modulus.
+            pub fn from_uint(n: U256) -> CtOption<Self> {
+                $field::from_repr(n.to_le_bytes())
+            }
+
+            /// Converts a field element into its canonical integer value as a [`U256`].
+            pub fn to_uint(&self) -> U256 {
+                U256::from_le_bytes(self.to_repr())
+            }
+        }
+
+        impl From<$field> for U256 {
+            fn from(a: $field) -> U256 {
+                a.to_uint()
+            }
+        }
+    };
+}
+
+impl_uint_conversions!(
+    Fp,
+    "40000000000000000000000000000000224698fc094cf91b992d30ed00000001"
+);
+impl_uint_conversions!(
+    Fq,
+    "40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::Field;
+
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    macro_rules! test_uint {
+        ($name:ident, $field:ident) => {
+            #[test]
+            fn $name() {
+                let mut rng = XorShiftRng::from_seed([
+                    0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54,
+                    0x06, 0xbc, 0xe5,
+                ]);
+
+                assert_eq!($field::zero().to_uint(), U256::ZERO);
+                assert_eq!($field::one().to_uint(), U256::ONE);
+                assert_eq!($field::from(7).to_uint(), U256::from_u64(7));
+
+                for _ in 0..100 {
+                    let a = $field::random(&mut rng);
+                    assert_eq!($field::from_uint(a.to_uint()).unwrap(), a);
+                    assert_eq!(U256::from(a), a.to_uint());
+                }
+
+                let m = $field::MODULUS_UINT;
+                assert_eq!(
+                    $field::from_uint(m.wrapping_sub(&U256::ONE)).unwrap(),
+                    -$field::one()
+                );
+                assert!(bool::from($field::from_uint(m).is_none()));
+                assert!(bool::from(
+                    $field::from_uint(m.wrapping_add(&U256::ONE)).is_none()
+                ));
+                assert_eq!(m, (-$field::one()).to_uint().wrapping_add(&U256::ONE));
+            }
+        };
+    }
+
+    test_uint!(test_fp_uint, Fp);
+    test_uint!(test_fq_uint, Fq);
+}
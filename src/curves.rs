@@ -3,28 +3,65 @@
 
 use core::cmp;
 use core::fmt;
+use core::hash;
 use core::iter::Sum;
 use core::ops::{Add, Mul, Neg, Sub};
 
 #[cfg(feature = "alloc")]
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "alloc")]
+use lazy_static::lazy_static;
 
 use ff::{Field, PrimeField};
 use group::{
     cofactor::{CofactorCurve, CofactorGroup},
     prime::{PrimeCurve, PrimeCurveAffine, PrimeGroup},
-    Curve as _, Group as _, GroupEncoding,
+    Curve as _, Group as _, GroupEncoding, UncompressedEncoding,
 };
-use rand::RngCore;
-use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use rand::distributions::{Distribution, Standard};
+use rand::{Rng, RngCore};
+use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq, CtOption};
 
-#[cfg(feature = "alloc")]
 use ff::WithSmallOrderMulGroup;
 
 use super::{Fp, Fq};
 
 #[cfg(feature = "alloc")]
-use crate::arithmetic::{Coordinates, CurveAffine, CurveExt};
+use crate::arithmetic::{Coordinates, CurveAffine, CurveCycle, CurveExt};
+use crate::DecodeError;
+
+/// The uncompressed encoding of a Pallas or Vesta point, which is the canonical
+/// encodings of its affine coordinates `x || y`, or 64 zero bytes for the point at
+/// infinity.
+///
+/// This is the [`group::UncompressedEncoding::Uncompressed`] type of
+/// [`EpAffine`] and [`EqAffine`]. Decoding it does not require a square root.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct UncompressedPoint(pub [u8; 64]);
+
+impl Default for UncompressedPoint {
+    fn default() -> Self {
+        UncompressedPoint([0; 64])
+    }
+}
+
+impl AsRef<[u8]> for UncompressedPoint {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for UncompressedPoint {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl fmt::Debug for UncompressedPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0[..].fmt(f)
+    }
+}
 
 macro_rules! new_curve_impl {
     (($($privacy:tt)*), $name:ident, $name_affine:ident, $iso:ident, $base:ident, $scalar:ident,
@@ -38,13 +75,42 @@ macro_rules! new_curve_impl {
             z: $base,
         }
 
+        impl_curve_mul!($name, $scalar, $curve_type);
+        impl_curve_double!($name, $curve_type);
+        impl_curve_endo!($name, $name_affine, $base, $scalar, $curve_type);
+        impl_curve_generator_table!($name, $name_affine, $scalar, $curve_type);
+
         impl $name {
+            /// The coefficient `a` of the curve equation `y^2 = x^3 + ax + b`.
+            pub const A: $base = $base::from_raw($a_raw);
+
+            /// The coefficient `b` of the curve equation `y^2 = x^3 + ax + b`.
+            pub const B: $base = $base::from_raw($b_raw);
+
             const fn curve_constant_a() -> $base {
-                $base::from_raw($a_raw)
+                Self::A
             }
 
             const fn curve_constant_b() -> $base {
-                $base::from_raw($b_raw)
+                Self::B
+            }
+        }
+
+        // These are unused on the isogenous curves.
+        #[allow(dead_code)]
+        impl $name {
+            /// The order of the group, which is the modulus of the scalar field, as
+            /// little-endian 64-bit limbs.
+            pub const ORDER: [u64; 4] = $scalar::MODULUS_LIMBS;
+
+            /// Returns the j-invariant `1728 * 4a^3 / (4a^3 + 27b^2)` of the curve,
+            /// which is zero for curves with `a = 0`.
+            pub fn j_invariant() -> $base {
+                let a3 = Self::A.square() * Self::A;
+                let four_a3 = a3.double().double();
+                let denominator = four_a3 + Self::B.square().mul_by_u64(27);
+                // The curve is not singular, so the denominator is nonzero.
+                four_a3.mul_by_u64(1728) * denominator.invert().unwrap()
             }
         }
 
@@ -67,6 +133,378 @@ macro_rules! new_curve_impl {
             }
         }
 
+        impl Distribution<$name> for Standard {
+            /// Samples a uniformly random point, as [`group::Group::random`] does.
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $name {
+                $name::random(rng)
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+        impl zeroize::DefaultIsZeroes for $name {}
+
+        #[cfg(feature = "zeroize")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+        impl zeroize::DefaultIsZeroes for $name_affine {}
+
+        impl $name_affine {
+            /// Attempts to decode a point from its compressed encoding, as
+            /// [`GroupEncoding::from_bytes`] does, but reports why decoding
+            /// failed.
+            ///
+            /// This is not constant time in the failure case.
+            pub fn try_from_bytes(bytes: &[u8; 32]) -> Result<Self, DecodeError> {
+                let mut tmp = *bytes;
+                let ysign = tmp[31] >> 7;
+                tmp[31] &= 0b0111_1111;
+
+                let x = $base::try_from_repr(tmp)?;
+                if bool::from(x.is_zero()) && ysign == 1 {
+                    return Err(DecodeError::InvalidCompressionFlag);
+                }
+                Option::from(Self::from_bytes(bytes)).ok_or(DecodeError::NotOnCurve)
+            }
+        }
+
+        // These are unused on the isogenous curves.
+        #[allow(dead_code)]
+        impl $name {
+            /// Decodes a point from its compressed encoding, as
+            /// [`GroupEncoding::from_bytes`] does, but returns `None` for the
+            /// identity.
+            pub fn from_bytes_reject_identity(bytes: &[u8; 32]) -> CtOption<Self> {
+                $name_affine::from_bytes_reject_identity(bytes).map(Self::from)
+            }
+
+            /// Returns the Jacobian x-coordinate `X` of this point, which represents the
+            /// affine point `(X / Z^2, Y / Z^3)`.
+            ///
+            /// The coordinates of a point are not unique, so this is only meaningful
+            /// together with [`Self::y`] and [`Self::z`].
+            pub fn x(&self) -> $base {
+                self.x
+            }
+
+            /// Returns the Jacobian y-coordinate `Y` of this point.
+            pub fn y(&self) -> $base {
+                self.y
+            }
+
+            /// Returns the Jacobian z-coordinate `Z` of this point, which is zero for
+            /// the identity.
+            pub fn z(&self) -> $base {
+                self.z
+            }
+
+            /// Constructs a point from its Jacobian coordinates without checking that
+            /// it is on the curve.
+            ///
+            /// The arithmetic in this crate assumes that points are on the curve, so
+            /// this must only be used for coordinates that are known to be valid (for
+            /// example, that were computed by custom formulas from valid points).
+            /// [`CurveExt::new_jacobian`](crate::arithmetic::CurveExt::new_jacobian)
+            /// checks the coordinates.
+            pub const fn new_unchecked(x: $base, y: $base, z: $base) -> Self {
+                $name { x, y, z }
+            }
+
+            /// Returns whether this point is on the curve (the identity is).
+            ///
+            /// This is always true unless an unchecked constructor such as
+            /// [`Self::new_unchecked`] was used. The curve has prime order, so every
+            /// point on the curve is also in the prime-order subgroup.
+            pub fn is_on_curve(&self) -> Choice {
+                // Y^2 = X^3 + AX(Z^4) + b(Z^6)
+                // Y^2 - (X^2 + A(Z^4))X = b(Z^6)
+
+                let z2 = self.z.square();
+                let z4 = z2.square();
+                let z6 = z4 * z2;
+                (self.y.square() - (self.x.square() + $name::curve_constant_a() * z4) * self.x)
+                    .ct_eq(&(z6 * $name::curve_constant_b()))
+                    | self.z.is_zero()
+            }
+
+            /// Checks that this point is a valid element of the group, i.e. that it is
+            /// on the curve.
+            ///
+            /// This is not constant time in the failure case.
+            pub fn validate(&self) -> Result<(), DecodeError> {
+                if bool::from(self.is_on_curve()) {
+                    Ok(())
+                } else {
+                    Err(DecodeError::NotOnCurve)
+                }
+            }
+        }
+
+        // These are unused on the isogenous curves.
+        #[allow(dead_code)]
+        impl $name_affine {
+            /// Decodes a point from its compressed encoding, as
+            /// [`GroupEncoding::from_bytes`] does, but returns `None` for the
+            /// identity.
+            ///
+            /// [`GroupEncoding::from_bytes`] accepts the all-zeroes encoding of the
+            /// identity, which is what [`GroupEncoding::to_bytes`] produces for it. This
+            /// is for protocols in which the identity is never a valid input, so that
+            /// it does not need to be checked for after decoding.
+            pub fn from_bytes_reject_identity(bytes: &[u8; 32]) -> CtOption<Self> {
+                Self::from_bytes(bytes).and_then(|p| CtOption::new(p, !p.is_identity()))
+            }
+
+            /// Returns the x-coordinate of this point, which is zero for the identity.
+            ///
+            /// [`CurveAffine::coordinates`](crate::arithmetic::CurveAffine::coordinates)
+            /// returns both coordinates, or `None` for the identity.
+            pub fn x(&self) -> $base {
+                self.x
+            }
+
+            /// Returns the y-coordinate of this point, which is zero for the identity.
+            pub fn y(&self) -> $base {
+                self.y
+            }
+
+            /// Constructs a point from its affine coordinates, returning `None` if it is
+            /// not on the curve. `(0, 0)` is the identity.
+            ///
+            /// This is the same as
+            /// [`CurveAffine::from_xy`](crate::arithmetic::CurveAffine::from_xy),
+            /// without requiring the trait to be in scope.
+            pub fn from_xy(x: $base, y: $base) -> CtOption<Self> {
+                let p = $name_affine { x, y };
+                CtOption::new(p, p.is_on_curve())
+            }
+
+            /// Constructs a point from its affine coordinates without checking that it
+            /// is on the curve.
+            ///
+            /// The arithmetic in this crate assumes that points are on the curve, so
+            /// this must only be used for coordinates that are known to be valid (for
+            /// example, that were returned by [`Self::x`] and [`Self::y`]).
+            pub const fn from_xy_unchecked(x: $base, y: $base) -> Self {
+                $name_affine { x, y }
+            }
+
+            /// Returns whether the y-coordinate of this point is odd, which is the sign
+            /// bit of its compressed encoding. This is false for the identity.
+            pub fn y_is_odd(&self) -> Choice {
+                self.y.is_odd()
+            }
+
+            /// Constructs the point with the x-coordinate `x` whose y-coordinate is odd
+            /// if `odd` is set, returning `None` if there is no point with this
+            /// x-coordinate.
+            ///
+            /// Unlike [`GroupEncoding::from_bytes`], this never returns the identity:
+            /// an `x` of zero is treated like any other x-coordinate.
+            pub fn from_x_and_parity(x: $base, odd: Choice) -> CtOption<Self> {
+                // y^2 = x^3 + ax + b
+                let y2 = (x.square() + &$name::curve_constant_a()) * x + &$name::curve_constant_b();
+                y2.sqrt().map(|y| $name_affine {
+                    x,
+                    y: $base::conditional_select(&y, &-y, y.is_odd() ^ odd),
+                })
+            }
+
+            /// Returns whether this point is on the curve (the identity is).
+            ///
+            /// This is always true unless an unchecked constructor such as
+            /// [`Self::from_xy_unchecked`] was used. The curve has prime order, so
+            /// every point on the curve is also in the prime-order subgroup.
+            pub fn is_on_curve(&self) -> Choice {
+                // y^2 - x^3 - ax ?= b
+                (self.y.square() - (self.x.square() + &$name::curve_constant_a()) * self.x)
+                    .ct_eq(&$name::curve_constant_b())
+                    | self.is_identity()
+            }
+
+            /// Checks that this point is a valid element of the group, i.e. that it is
+            /// on the curve.
+            ///
+            /// This is not constant time in the failure case.
+            pub fn validate(&self) -> Result<(), DecodeError> {
+                if bool::from(self.is_on_curve()) {
+                    Ok(())
+                } else {
+                    Err(DecodeError::NotOnCurve)
+                }
+            }
+
+            /// Encodes this point in the SEC1 format, with big-endian coordinates.
+            ///
+            /// The identity is encoded as `0x00`. Other points are encoded as `0x02` or
+            /// `0x03` (for an even or odd `y`) followed by `x` if `compressed` is set,
+            /// and as `0x04` followed by `x || y` otherwise.
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn to_sec1_bytes(self, compressed: bool) -> Vec<u8> {
+                if bool::from(self.is_identity()) {
+                    return alloc::vec![0x00];
+                }
+
+                let mut x = self.x.to_repr();
+                x.reverse();
+                let mut bytes = Vec::with_capacity(if compressed { 33 } else { 65 });
+                if compressed {
+                    bytes.push(0x02 | self.y.is_odd().unwrap_u8());
+                    bytes.extend_from_slice(&x);
+                } else {
+                    let mut y = self.y.to_repr();
+                    y.reverse();
+                    bytes.push(0x04);
+                    bytes.extend_from_slice(&x);
+                    bytes.extend_from_slice(&y);
+                }
+                bytes
+            }
+
+            /// Decodes a point from any of the SEC1 encodings produced by
+            /// [`Self::to_sec1_bytes`].
+            ///
+            /// This is not constant time.
+            pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+                fn coordinate(bytes: &[u8]) -> Result<$base, DecodeError> {
+                    let mut repr = crate::error::to_array(bytes)?;
+                    repr.reverse();
+                    $base::try_from_repr(repr)
+                }
+
+                let expected = match bytes.first() {
+                    Some(0x00) => 1,
+                    Some(0x02 | 0x03) => 33,
+                    Some(0x04) => 65,
+                    _ => return Err(DecodeError::InvalidHeader),
+                };
+                if bytes.len() != expected {
+                    return Err(DecodeError::InvalidLength {
+                        expected,
+                        actual: bytes.len(),
+                    });
+                }
+
+                match bytes[0] {
+                    0x00 => Ok(Self::identity()),
+                    0x04 => {
+                        let p = $name_affine {
+                            x: coordinate(&bytes[1..33])?,
+                            y: coordinate(&bytes[33..])?,
+                        };
+                        // (0, 0) represents the identity, which has its own encoding.
+                        if bool::from(p.is_identity()) {
+                            return Err(DecodeError::NotOnCurve);
+                        }
+                        p.validate()?;
+                        Ok(p)
+                    }
+                    prefix => {
+                        let x = coordinate(&bytes[1..])?;
+                        Option::from(Self::from_x_and_parity(x, Choice::from(prefix & 1)))
+                            .ok_or(DecodeError::NotOnCurve)
+                    }
+                }
+            }
+        }
+
+        // This is unused on the isogenous curves.
+        #[cfg(feature = "alloc")]
+        #[allow(dead_code)]
+        impl $name_affine {
+            /// Returns the pairwise sums `a[i] + b[i]` in affine coordinates,
+            /// performing a single field inversion for all of the slopes.
+            ///
+            /// This is the core of affine-bucket multiscalar multiplication. It
+            /// handles the identity, doubling and `a[i] == -b[i]` correctly, but it is
+            /// not constant time and must only be used on public points.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `a` and `b` have different lengths.
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn batch_add(a: &[Self], b: &[Self]) -> Vec<Self> {
+                assert_eq!(a.len(), b.len());
+
+                // The numerator and denominator of the slope of each sum, with a
+                // denominator of zero where no slope is needed.
+                let mut numerators = Vec::with_capacity(a.len());
+                let mut denominators = Vec::with_capacity(a.len());
+                for (a, b) in a.iter().zip(b.iter()) {
+                    let (n, d) = if bool::from(a.is_identity() | b.is_identity()) {
+                        ($base::zero(), $base::zero())
+                    } else if a.x != b.x {
+                        (b.y - a.y, b.x - a.x)
+                    } else if a.y == b.y {
+                        // There are no points of order 2, so `a.y` is nonzero.
+                        let xx = a.x.square();
+                        (xx + xx + xx + $name::curve_constant_a(), a.y + a.y)
+                    } else {
+                        ($base::zero(), $base::zero())
+                    };
+                    numerators.push(n);
+                    denominators.push(d);
+                }
+
+                $base::batch_invert(&mut denominators);
+
+                a.iter()
+                    .zip(b.iter())
+                    .zip(numerators.iter().zip(denominators.iter()))
+                    .map(|((a, b), (n, d))| {
+                        if bool::from(a.is_identity()) {
+                            *b
+                        } else if bool::from(b.is_identity()) {
+                            *a
+                        } else if bool::from(d.is_zero()) {
+                            // `a == -b`
+                            $name_affine::identity()
+                        } else {
+                            let lambda = n * d;
+                            let x = lambda.square() - a.x - b.x;
+                            let y = lambda * (a.x - x) - a.y;
+                            $name_affine { x, y }
+                        }
+                    })
+                    .collect()
+            }
+
+            /// Decodes each of `bytes` as [`GroupEncoding::from_bytes`] does.
+            ///
+            /// Decompression is dominated by one square root per point, which cannot
+            /// be shared between points, so with the `multicore` feature flag the
+            /// points are decoded in parallel. Without it, this is equivalent to
+            /// decoding the points one by one.
+            #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+            pub fn batch_from_bytes(bytes: &[[u8; 32]]) -> Vec<CtOption<Self>> {
+                #[cfg(feature = "multicore")]
+                {
+                    use rayon::prelude::*;
+
+                    bytes.par_iter().map(Self::from_bytes).collect()
+                }
+
+                #[cfg(not(feature = "multicore"))]
+                bytes.iter().map(Self::from_bytes).collect()
+            }
+        }
+
+        impl fmt::LowerHex for $name_affine {
+            /// Formats the canonical (compressed) encoding of this point in
+            /// lowercase hexadecimal, in byte order.
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                crate::fields::fmt_hex(&self.to_bytes(), false, f)
+            }
+        }
+
+        impl fmt::UpperHex for $name_affine {
+            /// Formats the canonical (compressed) encoding of this point in
+            /// uppercase hexadecimal, in byte order.
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                crate::fields::fmt_hex(&self.to_bytes(), true, f)
+            }
+        }
+
         impl group::Group for $name {
             type Scalar = $scalar;
 
@@ -109,7 +547,10 @@ macro_rules! new_curve_impl {
         #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
         impl group::WnafGroup for $name {
             fn recommended_wnaf_for_num_scalars(num_scalars: usize) -> usize {
-                // Copied from bls12_381::g1, should be updated.
+                // These are the thresholds from bls12_381::g1, which also has 255-bit
+                // scalars. Going from a window of w to w + 1 bits doubles the
+                // 2^(w - 1) table additions, and saves about 255 / (w + 1)(w + 2)
+                // additions per scalar, which gives these thresholds up to w = 14.
                 const RECOMMENDATIONS: [usize; 12] =
                     [1, 3, 7, 20, 43, 120, 273, 563, 1630, 3128, 7933, 62569];
 
@@ -155,15 +596,7 @@ macro_rules! new_curve_impl {
             }
 
             fn is_on_curve(&self) -> Choice {
-                // Y^2 = X^3 + AX(Z^4) + b(Z^6)
-                // Y^2 - (X^2 + A(Z^4))X = b(Z^6)
-
-                let z2 = self.z.square();
-                let z4 = z2.square();
-                let z6 = z4 * z2;
-                (self.y.square() - (self.x.square() + $name::curve_constant_a() * z4) * self.x)
-                    .ct_eq(&(z6 * $name::curve_constant_b()))
-                    | self.z.is_zero()
+                $name::is_on_curve(self)
             }
         }
 
@@ -359,45 +792,196 @@ macro_rules! new_curve_impl {
             }
         }
 
+        impl Sum<$name_affine> for $name {
+            fn sum<I>(iter: I) -> Self
+            where
+                I: Iterator<Item = $name_affine>,
+            {
+                iter.fold(Self::identity(), |acc, item| acc + item)
+            }
+        }
+
+        impl<'a> Sum<&'a $name_affine> for $name {
+            fn sum<I>(iter: I) -> Self
+            where
+                I: Iterator<Item = &'a $name_affine>,
+            {
+                iter.fold(Self::identity(), |acc, item| acc + item)
+            }
+        }
+
+        impl $name {
+            /// Computes `self + rhs` with the add-2007-bl formula, also returning
+            /// `h = u2 - u1` and `r = 2 (s2 - s1)`.
+            ///
+            /// The sum is only correct if neither point is the identity and
+            /// `self != ±rhs`. Both `h` and `r` are zero if `self == rhs`, and only `h`
+            /// is zero if `self == -rhs`, so callers can detect these cases.
+            fn add_formula(&self, rhs: &$name) -> ($name, $base, $base) {
+                // http://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#addition-add-2007-bl
+                let z1z1 = self.z.square();
+                let z2z2 = rhs.z.square();
+                let u1 = self.x * z2z2;
+                let u2 = rhs.x * z1z1;
+                let s1 = self.y * z2z2 * rhs.z;
+                let s2 = rhs.y * z1z1 * self.z;
+
+                let h = u2 - u1;
+                let i = (h + h).square();
+                let j = h * i;
+                let r = s2 - s1;
+                let r = r + r;
+                let v = u1 * i;
+                let x3 = r.square() - j - v - v;
+                let s1 = s1 * j;
+                let s1 = s1 + s1;
+                let y3 = r * (v - x3) - s1;
+                let z3 = (self.z + rhs.z).square() - z1z1 - z2z2;
+                let z3 = z3 * h;
+
+                ($name { x: x3, y: y3, z: z3 }, h, r)
+            }
+
+            /// Computes `self + rhs` with the madd-2007-bl formula, also returning
+            /// `h = u2 - x1` and `r = 2 (s2 - y1)`.
+            ///
+            /// This is the mixed counterpart of [`Self::add_formula`], with the same
+            /// exceptional cases.
+            fn add_mixed_formula(&self, rhs: &$name_affine) -> ($name, $base, $base) {
+                // http://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#addition-madd-2007-bl
+                let z1z1 = self.z.square();
+                let u2 = rhs.x * z1z1;
+                let s2 = rhs.y * z1z1 * self.z;
+
+                let h = u2 - self.x;
+                let hh = h.square();
+                let i = hh + hh;
+                let i = i + i;
+                let j = h * i;
+                let r = s2 - self.y;
+                let r = r + r;
+                let v = self.x * i;
+                let x3 = r.square() - j - v - v;
+                let j = self.y * j;
+                let j = j + j;
+                let y3 = r * (v - x3) - j;
+                let z3 = (self.z + h).square() - z1z1 - hh;
+
+                ($name { x: x3, y: y3, z: z3 }, h, r)
+            }
+        }
+
+        // These are unused on the isogenous curves.
+        #[allow(dead_code)]
+        impl $name {
+            /// Adds `rhs` to this point, without handling the exceptional cases.
+            ///
+            /// The result is unspecified if either point is the identity, or if
+            /// `self == rhs` or `self == -rhs`. This skips the checks for these cases,
+            /// so it is only suitable for inputs that are known to be distinct and
+            /// nonzero (such as when accumulating distinct random points).
+            pub fn add_incomplete(&self, rhs: &$name) -> $name {
+                self.add_formula(rhs).0
+            }
+
+            /// Adds the affine point `rhs` to this point, without handling the
+            /// exceptional cases.
+            ///
+            /// The result is unspecified if either point is the identity, or if
+            /// `self == rhs` or `self == -rhs`.
+            pub fn add_incomplete_affine(&self, rhs: &$name_affine) -> $name {
+                self.add_mixed_formula(rhs).0
+            }
+
+            /// Adds `rhs` to this point in constant time.
+            ///
+            /// Unlike `Add`, this does not branch on the exceptional cases: it always
+            /// computes both the generic sum and a doubling, and selects between them
+            /// and the inputs with [`ConditionallySelectable`]. It is used by the scalar
+            /// multiplications, where the operands depend on the scalar.
+            pub(crate) fn add_ct(&self, rhs: &$name) -> $name {
+                // If `self == -rhs` then `h` is zero but `r` is not, and so is `z3`:
+                // the generic formula already gives the identity.
+                let (res, h, r) = self.add_formula(rhs);
+                let res = $name::conditional_select(&res, &self.double(), h.is_zero() & r.is_zero());
+                let res = $name::conditional_select(&res, rhs, self.is_identity());
+                $name::conditional_select(&res, self, rhs.is_identity())
+            }
+
+            /// Adds the affine point `rhs` to this point in constant time.
+            ///
+            /// This is the mixed counterpart of [`Self::add_ct`].
+            pub(crate) fn add_mixed_ct(&self, rhs: &$name_affine) -> $name {
+                // As in `add_ct`, `z3` is zero if `self == -rhs`.
+                let (res, h, r) = self.add_mixed_formula(rhs);
+                let res = $name::conditional_select(&res, &self.double(), h.is_zero() & r.is_zero());
+                let res = $name::conditional_select(&res, &rhs.to_curve(), self.is_identity());
+                $name::conditional_select(&res, self, rhs.is_identity())
+            }
+
+            /// Returns `[2^k] self`, computed with `k` doublings.
+            ///
+            /// This runs in time that depends only on `k`.
+            pub fn mul_by_pow_2(&self, k: u32) -> $name {
+                // Doubling a point other than the identity never gives the identity,
+                // so only the input needs to be checked.
+                let mut acc = *self;
+                for _ in 0..k {
+                    acc = acc.double_incomplete();
+                }
+                $name::conditional_select(&acc, &$name::identity(), self.is_identity())
+            }
+
+            /// Returns `[k] self` for a small scalar `k`, using double-and-add over
+            /// the significant bits of `k`.
+            ///
+            /// This is not constant time: it leaks the bit length and Hamming weight
+            /// of `k`, so `k` must be public.
+            pub fn mul_u64(&self, k: u64) -> $name {
+                self.mul_u128(k.into())
+            }
+
+            /// Returns `[k] self` for a small scalar `k`, using double-and-add over
+            /// the significant bits of `k`.
+            ///
+            /// This is not constant time: it leaks the bit length and Hamming weight
+            /// of `k`, so `k` must be public.
+            pub fn mul_u128(&self, k: u128) -> $name {
+                let mut acc = $name::identity();
+                for i in (0..(128 - k.leading_zeros())).rev() {
+                    acc = acc.double();
+                    if (k >> i) & 1 == 1 {
+                        acc += self;
+                    }
+                }
+                acc
+            }
+        }
+
         impl<'a, 'b> Add<&'a $name> for &'b $name {
             type Output = $name;
 
             fn add(self, rhs: &'a $name) -> $name {
+                #[cfg(feature = "custom-backend")]
+                if let Some(res) = crate::backend::AddHook::add_hook(self, rhs) {
+                    return res;
+                }
+
                 if bool::from(self.is_identity()) {
                     *rhs
                 } else if bool::from(rhs.is_identity()) {
                     *self
                 } else {
-                    let z1z1 = self.z.square();
-                    let z2z2 = rhs.z.square();
-                    let u1 = self.x * z2z2;
-                    let u2 = rhs.x * z1z1;
-                    let s1 = self.y * z2z2 * rhs.z;
-                    let s2 = rhs.y * z1z1 * self.z;
-
-                    if u1 == u2 {
-                        if s1 == s2 {
+                    let (res, h, r) = self.add_formula(rhs);
+
+                    if bool::from(h.is_zero()) {
+                        if bool::from(r.is_zero()) {
                             self.double()
                         } else {
                             $name::identity()
                         }
                     } else {
-                        let h = u2 - u1;
-                        let i = (h + h).square();
-                        let j = h * i;
-                        let r = s2 - s1;
-                        let r = r + r;
-                        let v = u1 * i;
-                        let x3 = r.square() - j - v - v;
-                        let s1 = s1 * j;
-                        let s1 = s1 + s1;
-                        let y3 = r * (v - x3) - s1;
-                        let z3 = (self.z + rhs.z).square() - z1z1 - z2z2;
-                        let z3 = z3 * h;
-
-                        $name {
-                            x: x3, y: y3, z: z3
-                        }
+                        res
                     }
                 }
             }
@@ -412,34 +996,16 @@ macro_rules! new_curve_impl {
                 } else if bool::from(rhs.is_identity()) {
                     *self
                 } else {
-                    let z1z1 = self.z.square();
-                    let u2 = rhs.x * z1z1;
-                    let s2 = rhs.y * z1z1 * self.z;
+                    let (res, h, r) = self.add_mixed_formula(rhs);
 
-                    if self.x == u2 {
-                        if self.y == s2 {
+                    if bool::from(h.is_zero()) {
+                        if bool::from(r.is_zero()) {
                             self.double()
                         } else {
                             $name::identity()
                         }
                     } else {
-                        let h = u2 - self.x;
-                        let hh = h.square();
-                        let i = hh + hh;
-                        let i = i + i;
-                        let j = h * i;
-                        let r = s2 - self.y;
-                        let r = r + r;
-                        let v = self.x * i;
-                        let x3 = r.square() - j - v - v;
-                        let j = self.y * j;
-                        let j = j + j;
-                        let y3 = r * (v - x3) - j;
-                        let z3 = (self.z + h).square() - z1z1 - hh;
-
-                        $name {
-                            x: x3, y: y3, z: z3
-                        }
+                        res
                     }
                 }
             }
@@ -466,32 +1032,7 @@ macro_rules! new_curve_impl {
             type Output = $name;
 
             fn mul(self, other: &'b $scalar) -> Self::Output {
-                // TODO: make this faster
-
-                let mut acc = $name::identity();
-
-                // This is a simple double-and-add implementation of point
-                // multiplication, moving from most significant to least
-                // significant bit of the scalar.
-                //
-                // We don't use `PrimeFieldBits::.to_le_bits` here, because that would
-                // force users of this crate to depend on `bitvec` where they otherwise
-                // might not need to.
-                //
-                // NOTE: We skip the leading bit because it's always unset (we are turning
-                // the 32-byte repr into 256 bits, and $scalar::NUM_BITS = 255).
-                for bit in other
-                    .to_repr()
-                    .iter()
-                    .rev()
-                    .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
-                    .skip(1)
-                {
-                    acc = acc.double();
-                    acc = $name::conditional_select(&acc, &(acc + self), bit);
-                }
-
-                acc
+                self.mul_scalar(other)
             }
         }
 
@@ -581,32 +1122,7 @@ macro_rules! new_curve_impl {
             type Output = $name;
 
             fn mul(self, other: &'b $scalar) -> Self::Output {
-                // TODO: make this faster
-
-                let mut acc = $name::identity();
-
-                // This is a simple double-and-add implementation of point
-                // multiplication, moving from most significant to least
-                // significant bit of the scalar.
-                //
-                // We don't use `PrimeFieldBits::.to_le_bits` here, because that would
-                // force users of this crate to depend on `bitvec` where they otherwise
-                // might not need to.
-                //
-                // NOTE: We skip the leading bit because it's always unset (we are turning
-                // the 32-byte repr into 256 bits, and $scalar::NUM_BITS = 255).
-                for bit in other
-                    .to_repr()
-                    .iter()
-                    .rev()
-                    .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
-                    .skip(1)
-                {
-                    acc = acc.double();
-                    acc = $name::conditional_select(&acc, &(acc + self), bit);
-                }
-
-                acc
+                self.to_curve().mul_scalar(other)
             }
         }
 
@@ -704,6 +1220,34 @@ macro_rules! new_curve_impl {
             }
         }
 
+        impl UncompressedEncoding for $name_affine {
+            type Uncompressed = UncompressedPoint;
+
+            fn from_uncompressed(bytes: &UncompressedPoint) -> CtOption<Self> {
+                Self::from_uncompressed_unchecked(bytes)
+                    .and_then(|p| CtOption::new(p, p.is_on_curve()))
+            }
+
+            fn from_uncompressed_unchecked(bytes: &UncompressedPoint) -> CtOption<Self> {
+                let mut x = [0; 32];
+                let mut y = [0; 32];
+                x.copy_from_slice(&bytes.0[..32]);
+                y.copy_from_slice(&bytes.0[32..]);
+
+                // The identity is encoded as (0, 0), which is not on the curve.
+                $base::from_repr(x).and_then(|x| {
+                    $base::from_repr(y).map(|y| $name_affine { x, y })
+                })
+            }
+
+            fn to_uncompressed(&self) -> UncompressedPoint {
+                let mut bytes = [0; 64];
+                bytes[..32].copy_from_slice(&self.x.to_repr());
+                bytes[32..].copy_from_slice(&self.y.to_repr());
+                UncompressedPoint(bytes)
+            }
+        }
+
         #[cfg(feature = "alloc")]
         #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
         impl CurveAffine for $name_affine {
@@ -712,9 +1256,7 @@ macro_rules! new_curve_impl {
             type CurveExt = $name;
 
             fn is_on_curve(&self) -> Choice {
-                // y^2 - x^3 - ax ?= b
-                (self.y.square() - (self.x.square() + &$name::curve_constant_a()) * self.x).ct_eq(&$name::curve_constant_b())
-                    | self.is_identity()
+                $name_affine::is_on_curve(self)
             }
 
             fn coordinates(&self) -> CtOption<Coordinates<Self>> {
@@ -722,10 +1264,11 @@ macro_rules! new_curve_impl {
             }
 
             fn from_xy(x: Self::Base, y: Self::Base) -> CtOption<Self> {
-                let p = $name_affine {
-                    x, y,
-                };
-                CtOption::new(p, p.is_on_curve())
+                $name_affine::from_xy(x, y)
+            }
+
+            fn from_xy_unchecked(x: Self::Base, y: Self::Base) -> Self {
+                $name_affine::from_xy_unchecked(x, y)
             }
 
             fn a() -> Self::Base {
@@ -769,6 +1312,37 @@ macro_rules! new_curve_impl {
 
         impl cmp::Eq for $name_affine {}
 
+        impl hash::Hash for $name_affine {
+            fn hash<H: hash::Hasher>(&self, state: &mut H) {
+                self.to_bytes().hash(state);
+            }
+        }
+
+        /// Points are ordered by their compressed encodings, compared
+        /// lexicographically as byte strings. This order is canonical but unrelated to
+        /// the group structure.
+        impl cmp::Ord for $name_affine {
+            fn cmp(&self, other: &Self) -> cmp::Ordering {
+                self.to_bytes().cmp(&other.to_bytes())
+            }
+        }
+
+        impl cmp::PartialOrd for $name_affine {
+            fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<'a> TryFrom<&'a [u8]> for $name_affine {
+            type Error = DecodeError;
+
+            /// Decodes a point from its 32-byte compressed encoding, as produced by
+            /// [`GroupEncoding::to_bytes`].
+            fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                Self::try_from_bytes(&crate::error::to_array(bytes)?)
+            }
+        }
+
         impl ConditionallySelectable for $name_affine {
             fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
                 $name_affine {
@@ -794,6 +1368,408 @@ macro_rules! new_curve_impl {
     };
 }
 
+macro_rules! impl_curve_endo {
+    ($name:ident, $name_affine:ident, $base:ident, $scalar:ident, special_a0_b5) => {
+        impl $name {
+            /// The scalar by which [`Self::endo`] multiplies points:
+            /// `p.endo() == p * Self::ZETA_SCALAR` for every point `p`.
+            pub const ZETA_SCALAR: $scalar = <$scalar as WithSmallOrderMulGroup<3>>::ZETA;
+
+            /// Applies the curve endomorphism `(x, y) -> (ZETA * x, y)`, where `ZETA`
+            /// is the cube root of unity of the base field. This costs a single field
+            /// multiplication.
+            pub fn endo(&self) -> Self {
+                $name {
+                    x: self.x * $base::ZETA,
+                    y: self.y,
+                    z: self.z,
+                }
+            }
+        }
+
+        impl $name_affine {
+            /// The scalar by which [`Self::endo`] multiplies points:
+            /// `p.endo() == p * Self::ZETA_SCALAR` for every point `p`.
+            pub const ZETA_SCALAR: $scalar = $name::ZETA_SCALAR;
+
+            /// Applies the curve endomorphism `(x, y) -> (ZETA * x, y)`, where `ZETA`
+            /// is the cube root of unity of the base field. This costs a single field
+            /// multiplication, and maps the identity to itself.
+            pub fn endo(&self) -> Self {
+                $name_affine {
+                    x: self.x * $base::ZETA,
+                    y: self.y,
+                }
+            }
+        }
+    };
+    ($name:ident, $name_affine:ident, $base:ident, $scalar:ident, general) => {};
+}
+
+/// Recodes a little-endian scalar encoding of at most 255 bits into 64 signed
+/// radix-16 digits. The first 63 digits are in `[-8, 8)`, and the last one is in
+/// `[0, 8]`.
+#[cfg(feature = "alloc")]
+fn radix_16_digits(bytes: &[u8; 32]) -> [i8; 64] {
+    let mut digits = [0i8; 64];
+    for (i, byte) in bytes.iter().enumerate() {
+        digits[2 * i] = (byte & 15) as i8;
+        digits[2 * i + 1] = (byte >> 4) as i8;
+    }
+
+    let mut carry = 0;
+    for digit in digits[..63].iter_mut() {
+        *digit += carry;
+        carry = (*digit + 8) >> 4;
+        *digit -= carry << 4;
+    }
+    digits[63] += carry;
+
+    digits
+}
+
+macro_rules! impl_curve_generator_table {
+    ($name:ident, $name_affine:ident, $scalar:ident, special_a0_b5) => {
+        impl $name {
+            /// Multiplies the generator by `k`.
+            ///
+            /// With the `alloc` feature flag this uses a radix-16 table of multiples of
+            /// the generator, built on first use: `k` is recoded into 64 signed digits
+            /// in `[-8, 8]`, and the result is the sum of one table entry per digit,
+            /// with no doublings. The table is scanned in full for each digit, so no
+            /// memory access depends on `k`, and the entries are added with a
+            /// constant-time formula, so zero digits (which select the identity) take
+            /// the same time as the others. Without `alloc`, this multiplies the
+            /// generator by `k` with [`Self::mul_glv`].
+            pub fn mul_generator(k: &$scalar) -> $name {
+                #[cfg(feature = "alloc")]
+                {
+                    lazy_static! {
+                        /// `TABLE[i][j] = (j + 1) * 16^i * G`.
+                        static ref TABLE: Vec<[$name_affine; 8]> = {
+                            let mut table = Vec::with_capacity(64);
+                            let mut base = $name::generator();
+                            for _ in 0..64 {
+                                let mut row = [$name::identity(); 8];
+                                row[0] = base;
+                                for j in 1..8 {
+                                    row[j] = row[j - 1] + base;
+                                }
+                                let mut affine = [$name_affine::identity(); 8];
+                                $name::batch_normalize(&row, &mut affine);
+                                table.push(affine);
+                                base = row[7].double();
+                            }
+                            table
+                        };
+                    }
+
+                    let digits = radix_16_digits(&k.to_repr());
+                    let mut acc = $name::identity();
+                    for (row, digit) in TABLE.iter().zip(digits.iter()) {
+                        let neg = Choice::from((*digit as u8) >> 7);
+                        let abs = (*digit as u8 ^ 0u8.wrapping_sub(neg.unwrap_u8()))
+                            .wrapping_add(neg.unwrap_u8());
+
+                        let mut t = $name_affine::identity();
+                        for (j, p) in row.iter().enumerate() {
+                            t.conditional_assign(p, abs.ct_eq(&(j as u8 + 1)));
+                        }
+                        t.conditional_negate(neg);
+                        acc = acc.add_mixed_ct(&t);
+                    }
+                    acc
+                }
+
+                #[cfg(not(feature = "alloc"))]
+                {
+                    $name::generator().mul_glv(k)
+                }
+            }
+        }
+    };
+    ($name:ident, $name_affine:ident, $scalar:ident, general) => {};
+}
+
+/// Returns the width-5 NAF of the value of a little-endian encoding that is less
+/// than `2^128`: digits that are zero or odd in `(-16, 16)`, with at most one non-zero
+/// digit in any 5 consecutive ones.
+fn wnaf_5_digits(bytes: &[u8; 32]) -> [i8; 129] {
+    let mut k = [0u64; 3];
+    for (limb, chunk) in k.iter_mut().zip(bytes[..16].chunks(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    let mut digits = [0i8; 129];
+    for digit in digits.iter_mut() {
+        if k[0] & 1 == 1 {
+            let d = (k[0] & 31) as i8;
+            if d < 16 {
+                // Clears the low 5 bits.
+                k[0] -= d as u64;
+                *digit = d;
+            } else {
+                // Adds 32 - d, which also clears the low 5 bits.
+                let (k0, carry) = k[0].overflowing_add((32 - d) as u64);
+                let (k1, carry) = k[1].overflowing_add(carry as u64);
+                k = [k0, k1, k[2] + carry as u64];
+                *digit = d - 32;
+            }
+        }
+
+        k = [
+            (k[0] >> 1) | (k[1] << 63),
+            (k[1] >> 1) | (k[2] << 63),
+            k[2] >> 1,
+        ];
+    }
+    debug_assert_eq!(k, [0; 3]);
+
+    digits
+}
+
+macro_rules! impl_curve_mul {
+    ($name:ident, $scalar:ident, special_a0_b5) => {
+        impl $name {
+            /// Multiplies this point by `k` using the curve endomorphism.
+            ///
+            /// The scalar is decomposed as `k = k1 + k2 * ZETA` with `k1` and `k2` of
+            /// at most 128 bits, and `[k1] P + [k2] endo(P)` is computed with a single
+            /// chain of 128 doublings (Strauss-Shamir). Neither the decomposition nor
+            /// the loop branches on the scalar: the additions use the constant-time
+            /// formula rather than `Add`, which has early returns for the identity and
+            /// doubling cases. This is what the `Mul` impls use.
+            pub fn mul_glv(&self, k: &$scalar) -> $name {
+                let (k1, k1_neg, k2, k2_neg) = k.glv_decompose();
+
+                let mut p1 = *self;
+                p1.conditional_negate(k1_neg);
+                let mut p2 = self.endo();
+                p2.conditional_negate(k2_neg);
+                let p12 = p1.add_ct(&p2);
+
+                let k1 = k1.to_repr();
+                let k2 = k2.to_repr();
+                let mut acc = $name::identity();
+                for i in (0..128).rev() {
+                    let b1 = Choice::from((k1[i / 8] >> (i % 8)) & 1);
+                    let b2 = Choice::from((k2[i / 8] >> (i % 8)) & 1);
+
+                    let t = $name::conditional_select(&p1, &p2, b2);
+                    let t = $name::conditional_select(&t, &p12, b1 & b2);
+
+                    acc = acc.double();
+                    acc = $name::conditional_select(&acc, &acc.add_ct(&t), b1 | b2);
+                }
+
+                acc
+            }
+
+            fn mul_scalar(&self, k: &$scalar) -> $name {
+                self.mul_glv(k)
+            }
+
+            /// Computes `a * p + b * q`.
+            ///
+            /// Both scalars are decomposed as in [`Self::mul_glv`], and the four
+            /// half-width products share a single chain of 128 doublings, each followed
+            /// by one addition of an entry from a table of the 16 subset sums of
+            /// `p, endo(p), q, endo(q)`. This is about twice as fast as two scalar
+            /// multiplications. It does not branch on the scalars: the table is built
+            /// and accumulated with the constant-time addition formula, which handles
+            /// the identity entry and coinciding points without early returns.
+            pub fn multiscalar_mul_2(a: &$scalar, p: &$name, b: &$scalar, q: &$name) -> $name {
+                let (a1, a1_neg, a2, a2_neg) = a.glv_decompose();
+                let (b1, b1_neg, b2, b2_neg) = b.glv_decompose();
+
+                let mut points = [*p, p.endo(), *q, q.endo()];
+                for (point, neg) in points.iter_mut().zip([a1_neg, a2_neg, b1_neg, b2_neg]) {
+                    point.conditional_negate(neg);
+                }
+
+                // table[i] is the sum of the points[j] for the bits j set in i.
+                let mut table = [$name::identity(); 16];
+                for i in 1..16 {
+                    table[i] = table[i & (i - 1)].add_ct(&points[i.trailing_zeros() as usize]);
+                }
+
+                let scalars = [a1.to_repr(), a2.to_repr(), b1.to_repr(), b2.to_repr()];
+                let mut acc = $name::identity();
+                for i in (0..128).rev() {
+                    let index = scalars.iter().enumerate().fold(0u8, |index, (j, s)| {
+                        index | (((s[i / 8] >> (i % 8)) & 1) << j)
+                    });
+
+                    let mut t = $name::identity();
+                    for (j, entry) in table.iter().enumerate() {
+                        t.conditional_assign(entry, index.ct_eq(&(j as u8)));
+                    }
+
+                    acc = acc.double().add_ct(&t);
+                }
+
+                acc
+            }
+
+            /// Computes `a * p + b * q` in variable time.
+            ///
+            /// Both scalars are decomposed as in [`Self::mul_glv`], and the four
+            /// half-width products are computed with interleaved width-5 NAFs, which
+            /// share 128 doublings and need about 85 additions in total.
+            ///
+            /// This must only be used with public scalars, such as when verifying
+            /// signatures; use [`Self::multiscalar_mul_2`] otherwise.
+            pub fn multiscalar_mul_2_vartime(
+                a: &$scalar,
+                p: &$name,
+                b: &$scalar,
+                q: &$name,
+            ) -> $name {
+                let (a1, a1_neg, a2, a2_neg) = a.glv_decompose();
+                let (b1, b1_neg, b2, b2_neg) = b.glv_decompose();
+
+                let terms = [
+                    (a1, a1_neg, *p),
+                    (a2, a2_neg, p.endo()),
+                    (b1, b1_neg, *q),
+                    (b2, b2_neg, q.endo()),
+                ];
+                let mut digits = [[0i8; 129]; 4];
+                let mut tables = [[$name::identity(); 8]; 4];
+                for ((k, neg, point), (digits, table)) in
+                    terms.iter().zip(digits.iter_mut().zip(tables.iter_mut()))
+                {
+                    *digits = wnaf_5_digits(&k.to_repr());
+
+                    // The odd multiples point, 3 * point, ..., 15 * point.
+                    let point = if bool::from(*neg) { -point } else { *point };
+                    let double = point.double();
+                    table[0] = point;
+                    for j in 1..8 {
+                        table[j] = table[j - 1] + double;
+                    }
+                }
+
+                let mut acc = $name::identity();
+                for i in (0..129).rev() {
+                    acc = acc.double();
+                    for (digits, table) in digits.iter().zip(tables.iter()) {
+                        let d = digits[i];
+                        if d > 0 {
+                            acc += table[(d / 2) as usize];
+                        } else if d < 0 {
+                            acc -= table[(-d / 2) as usize];
+                        }
+                    }
+                }
+
+                acc
+            }
+        }
+    };
+    ($name:ident, $scalar:ident, general) => {
+        impl $name {
+            fn mul_scalar(&self, k: &$scalar) -> $name {
+                let mut acc = $name::identity();
+
+                // This is a simple double-and-add implementation of point
+                // multiplication, moving from most significant to least
+                // significant bit of the scalar.
+                //
+                // We don't use `PrimeFieldBits::.to_le_bits` here, because that would
+                // force users of this crate to depend on `bitvec` where they otherwise
+                // might not need to.
+                //
+                // NOTE: We skip the leading bit because it's always unset (we are turning
+                // the 32-byte repr into 256 bits, and $scalar::NUM_BITS = 255).
+                for bit in k
+                    .to_repr()
+                    .iter()
+                    .rev()
+                    .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
+                    .skip(1)
+                {
+                    acc = acc.double();
+                    acc = $name::conditional_select(&acc, &(acc + self), bit);
+                }
+
+                acc
+            }
+        }
+    };
+}
+
+macro_rules! impl_curve_double {
+    ($name:ident, special_a0_b5) => {
+        impl $name {
+            /// Doubles this point, without handling the exceptional case.
+            ///
+            /// The result is unspecified if this point is the identity. Use
+            /// [`Group::double`](group::Group::double) unless that case is excluded.
+            pub fn double_incomplete(&self) -> $name {
+                // http://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian-0.html#doubling-dbl-2009-l
+                //
+                // There are no points of order 2.
+
+                let a = self.x.square();
+                let b = self.y.square();
+                let c = b.square();
+                let d = self.x + b;
+                let d = d.square();
+                let d = d - a - c;
+                let d = d + d;
+                let e = a + a + a;
+                let f = e.square();
+                let z3 = self.z * self.y;
+                let z3 = z3 + z3;
+                let x3 = f - (d + d);
+                let c = c + c;
+                let c = c + c;
+                let c = c + c;
+                let y3 = e * (d - x3) - c;
+
+                $name {
+                    x: x3,
+                    y: y3,
+                    z: z3,
+                }
+            }
+        }
+    };
+    ($name:ident, general) => {
+        impl $name {
+            /// Doubles this point, without handling the exceptional case.
+            ///
+            /// The result is unspecified if this point is the identity. Use
+            /// [`Group::double`](group::Group::double) unless that case is excluded.
+            pub fn double_incomplete(&self) -> $name {
+                // http://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#doubling-dbl-2007-bl
+                //
+                // There are no points of order 2.
+
+                let xx = self.x.square();
+                let yy = self.y.square();
+                let a = yy.square();
+                let zz = self.z.square();
+                let s = ((self.x + yy).square() - xx - a).double();
+                let m = xx.triple() + $name::curve_constant_a() * zz.square();
+                let x3 = m.square() - s.double();
+                let a = a.double();
+                let a = a.double();
+                let a = a.double();
+                let y3 = m * (s - x3) - a;
+                let z3 = (self.y + self.z).square() - yy - zz;
+
+                $name {
+                    x: x3,
+                    y: y3,
+                    z: z3,
+                }
+            }
+        }
+    };
+}
+
 macro_rules! impl_projective_curve_specific {
     ($name:ident, $base:ident, special_a0_b5) => {
         fn generator() -> Self {
@@ -810,34 +1786,11 @@ macro_rules! impl_projective_curve_specific {
         }
 
         fn double(&self) -> Self {
-            // http://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian-0.html#doubling-dbl-2009-l
-            //
-            // There are no points of order 2.
-
-            let a = self.x.square();
-            let b = self.y.square();
-            let c = b.square();
-            let d = self.x + b;
-            let d = d.square();
-            let d = d - a - c;
-            let d = d + d;
-            let e = a + a + a;
-            let f = e.square();
-            let z3 = self.z * self.y;
-            let z3 = z3 + z3;
-            let x3 = f - (d + d);
-            let c = c + c;
-            let c = c + c;
-            let c = c + c;
-            let y3 = e * (d - x3) - c;
-
-            let tmp = $name {
-                x: x3,
-                y: y3,
-                z: z3,
-            };
-
-            $name::conditional_select(&tmp, &$name::identity(), self.is_identity())
+            $name::conditional_select(
+                &self.double_incomplete(),
+                &$name::identity(),
+                self.is_identity(),
+            )
         }
     };
     ($name:ident, $base:ident, general) => {
@@ -847,30 +1800,11 @@ macro_rules! impl_projective_curve_specific {
         }
 
         fn double(&self) -> Self {
-            // http://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#doubling-dbl-2007-bl
-            //
-            // There are no points of order 2.
-
-            let xx = self.x.square();
-            let yy = self.y.square();
-            let a = yy.square();
-            let zz = self.z.square();
-            let s = ((self.x + yy).square() - xx - a).double();
-            let m = xx.double() + xx + $name::curve_constant_a() * zz.square();
-            let x3 = m.square() - s.double();
-            let a = a.double();
-            let a = a.double();
-            let a = a.double();
-            let y3 = m * (s - x3) - a;
-            let z3 = (self.y + self.z).square() - yy - zz;
-
-            let tmp = $name {
-                x: x3,
-                y: y3,
-                z: z3,
-            };
-
-            $name::conditional_select(&tmp, &$name::identity(), self.is_identity())
+            $name::conditional_select(
+                &self.double_incomplete(),
+                &$name::identity(),
+                self.is_identity(),
+            )
         }
     };
 }
@@ -879,35 +1813,13 @@ macro_rules! impl_projective_curve_specific {
 macro_rules! impl_projective_curve_ext {
     ($name:ident, $iso:ident, $base:ident, special_a0_b5) => {
         fn hash_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
-            use super::hashtocurve;
-
-            Box::new(move |message| {
-                let mut us = [Field::ZERO; 2];
-                hashtocurve::hash_to_field($name::CURVE_ID, domain_prefix, message, &mut us);
-                let q0 = hashtocurve::map_to_curve_simple_swu::<$base, $name, $iso>(
-                    &us[0],
-                    $name::THETA,
-                    $name::Z,
-                );
-                let q1 = hashtocurve::map_to_curve_simple_swu::<$base, $name, $iso>(
-                    &us[1],
-                    $name::THETA,
-                    $name::Z,
-                );
-                let r = q0 + &q1;
-                debug_assert!(bool::from(r.is_on_curve()));
-                hashtocurve::iso_map::<$base, $name, $iso>(&r, &$name::ISOGENY_CONSTANTS)
-            })
+            $name::hash_to_curve_with_hash::<super::hashtocurve::Blake2b512>(domain_prefix)
         }
 
         /// Apply the curve endomorphism by multiplying the x-coordinate
         /// by an element of multiplicative order 3.
         fn endo(&self) -> Self {
-            $name {
-                x: self.x * $base::ZETA,
-                y: self.y,
-                z: self.z,
-            }
+            $name::endo(self)
         }
     };
     ($name:ident, $iso:ident, $base:ident, general) => {
@@ -923,6 +1835,107 @@ macro_rules! impl_projective_curve_ext {
     };
 }
 
+#[cfg(feature = "alloc")]
+macro_rules! impl_hash_to_curve_suites {
+    ($name:ident, $iso:ident, $base:ident, $curve_id:literal) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl $name {
+            /// The ID of the RFC 9380 hash-to-curve suite implemented by
+            /// [`Self::hash_to_curve_sha256`].
+            #[cfg(feature = "hash-to-curve-sha2")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "hash-to-curve-sha2")))]
+            pub const SHA256_SUITE_ID: &'static str = concat!($curve_id, "_XMD:SHA-256_SSWU_RO_");
+
+            /// Returns a function that hashes messages to the curve in the same way as
+            /// [`CurveExt::hash_to_curve`], but with the hash function `H` in place of
+            /// [`Blake2b512`](crate::hashtocurve::Blake2b512).
+            ///
+            /// The domain separation tag is
+            /// `{domain_prefix}-{CURVE_ID}_XMD:{H::NAME}_SSWU_RO_`. To hash to the curve
+            /// with SHA-256, use `hash_to_curve_sha256` (with the `hash-to-curve-sha2`
+            /// feature flag), which implements the RFC 9380 suite with that suite ID
+            /// (see [`PrefixedXmdHash`](crate::hashtocurve::PrefixedXmdHash)).
+            ///
+            /// # Panics
+            ///
+            /// The returned function panics if the domain separation tag is longer than
+            /// 255 bytes.
+            pub fn hash_to_curve_with_hash<'a, H: crate::hashtocurve::PrefixedXmdHash>(
+                domain_prefix: &'a str,
+            ) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+                use crate::hashtocurve;
+
+                Box::new(move |message| {
+                    let mut us = [$base::zero(); 2];
+                    hashtocurve::hash_to_field::<H, _>(
+                        $name::CURVE_ID,
+                        domain_prefix,
+                        message,
+                        &mut us,
+                    );
+                    hashtocurve::map_to_curve::<$base, $name, $iso>(
+                        &us,
+                        $name::THETA,
+                        $name::Z,
+                        &$name::ISOGENY_CONSTANTS,
+                    )
+                })
+            }
+
+            /// Returns a function that hashes messages to the curve with the RFC 9380
+            /// suite [`Self::SHA256_SUITE_ID`], which uses `expand_message_xmd` with
+            /// SHA-256 and the simplified SWU map to an isogenous curve.
+            ///
+            /// This is [`Self::hash_to_curve_with`] for
+            /// [`ExpandMsgXmd<Sha256>`](crate::hashtocurve::ExpandMsgXmd).
+            ///
+            /// # Panics
+            ///
+            /// The returned function panics if `dst` is longer than 255 bytes.
+            #[cfg(feature = "hash-to-curve-sha2")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "hash-to-curve-sha2")))]
+            pub fn hash_to_curve_sha256<'a>(dst: &'a [u8]) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+                Self::hash_to_curve_with::<
+                    crate::hashtocurve::ExpandMsgXmd<crate::hashtocurve::Sha256>,
+                >(dst)
+            }
+
+            /// Returns a function that hashes messages to the curve as specified by
+            /// RFC 9380, using the message expansion `E` and the simplified SWU map to
+            /// an isogenous curve.
+            ///
+            /// Unlike [`CurveExt::hash_to_curve`], which derives its own domain
+            /// separation tag from a prefix, `dst` is the complete domain separation
+            /// tag, so that the results match other implementations of the suite. It
+            /// should end with the suite ID, as in `MYAPP-V01-CS01-with-<suite ID>`,
+            /// where the suite ID for [`ExpandMsgXof<Shake128>`] is
+            /// `<curve>_XOF:SHAKE128_SSWU_RO_`.
+            ///
+            /// [`ExpandMsgXof<Shake128>`]: crate::hashtocurve::ExpandMsgXof
+            ///
+            /// # Panics
+            ///
+            /// The returned function may panic if `dst` is longer than 255 bytes.
+            pub fn hash_to_curve_with<'a, E: crate::hashtocurve::ExpandMessage>(
+                dst: &'a [u8],
+            ) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+                use crate::hashtocurve;
+
+                Box::new(move |message| {
+                    let mut us = [$base::zero(); 2];
+                    hashtocurve::hash_to_field_with::<E, _>(dst, message, &mut us);
+                    hashtocurve::map_to_curve::<$base, $name, $iso>(
+                        &us,
+                        $name::THETA,
+                        $name::Z,
+                        &$name::ISOGENY_CONSTANTS,
+                    )
+                })
+            }
+        }
+    };
+}
+
 macro_rules! impl_affine_curve_specific {
     ($name:ident, $base:ident, special_a0_b5) => {
         fn generator() -> Self {
@@ -1004,6 +2017,23 @@ new_curve_impl!(
     general
 );
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl CurveCycle for Ep {
+    type Sibling = Eq;
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl CurveCycle for Eq {
+    type Sibling = Ep;
+}
+
+#[cfg(feature = "alloc")]
+impl_hash_to_curve_suites!(Ep, IsoEp, Fp, "pallas");
+#[cfg(feature = "alloc")]
+impl_hash_to_curve_suites!(Eq, IsoEq, Fq, "vesta");
+
 impl Ep {
     /// Constants used for computing the isogeny from IsoEp to Ep.
     pub const ISOGENY_CONSTANTS: [Fp; 13] = [
@@ -1087,6 +2117,12 @@ impl Ep {
         ]),
     ];
 
+    /// The coefficient `a` of the curve IsoEp, which is isogenous to Ep.
+    pub const ISO_A: Fp = IsoEp::A;
+
+    /// The coefficient `b` of the curve IsoEp, which is isogenous to Ep.
+    pub const ISO_B: Fp = IsoEp::B;
+
     /// Z = -13
     pub const Z: Fp = Fp::from_raw([
         0x992d30ecfffffff4,
@@ -1187,6 +2223,12 @@ impl Eq {
         ]),
     ];
 
+    /// The coefficient `a` of the curve IsoEq, which is isogenous to Eq.
+    pub const ISO_A: Fq = IsoEq::A;
+
+    /// The coefficient `b` of the curve IsoEq, which is isogenous to Eq.
+    pub const ISO_B: Fq = IsoEq::B;
+
     /// Z = -13
     pub const Z: Fq = Fq::from_raw([
         0x8c46eb20fffffff4,
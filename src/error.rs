@@ -0,0 +1,57 @@
+//! Error types shared by the field and curve modules.
+
+use core::fmt;
+
+/// An error which can be returned when decoding a field element or point from
+/// bytes, or when validating a point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input did not have the expected length.
+    InvalidLength {
+        /// The length of a valid encoding.
+        expected: usize,
+        /// The length of the input.
+        actual: usize,
+    },
+    /// The input is not the canonical encoding of a field element, i.e. its
+    /// value is not less than the field modulus.
+    NonCanonical,
+    /// The input encodes an `x`-coordinate for which there is no point on the
+    /// curve.
+    NotOnCurve,
+    /// The compression flag (the `y`-coordinate sign bit) is set in an encoding
+    /// of the point at infinity.
+    InvalidCompressionFlag,
+    /// The header of an encoding is invalid, such as the prefix byte of a SEC1
+    /// encoding or the header of an encoding of precomputed tables.
+    InvalidHeader,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::InvalidLength { expected, actual } => {
+                write!(f, "expected {} bytes, found {}", expected, actual)
+            }
+            DecodeError::NonCanonical => write!(f, "non-canonical field element encoding"),
+            DecodeError::NotOnCurve => write!(f, "point is not on the curve"),
+            DecodeError::InvalidCompressionFlag => {
+                write!(f, "compression flag is set for the point at infinity")
+            }
+            DecodeError::InvalidHeader => write!(f, "invalid header"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for DecodeError {}
+
+/// Converts a slice into a fixed-size array, checking its length.
+pub(crate) fn to_array(bytes: &[u8]) -> Result<[u8; 32], DecodeError> {
+    bytes.try_into().map_err(|_| DecodeError::InvalidLength {
+        expected: 32,
+        actual: bytes.len(),
+    })
+}
@@ -7,6 +7,181 @@ mod fq;
 pub use fp::*;
 pub use fq::*;
 
+use core::fmt;
+
+use ff::PrimeField;
+use subtle::CtOption;
+
+use crate::arithmetic::mac;
+
+/// An error which can be returned when parsing a field element from a string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseFieldError {
+    kind: ParseFieldErrorKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ParseFieldErrorKind {
+    Empty,
+    InvalidDigit,
+    OutOfRange,
+}
+
+impl ParseFieldError {
+    const fn new(kind: ParseFieldErrorKind) -> Self {
+        ParseFieldError { kind }
+    }
+
+    pub(crate) const fn out_of_range() -> Self {
+        Self::new(ParseFieldErrorKind::OutOfRange)
+    }
+}
+
+impl fmt::Display for ParseFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseFieldErrorKind::Empty => write!(f, "cannot parse field element from empty string"),
+            ParseFieldErrorKind::InvalidDigit => write!(f, "invalid digit found in string"),
+            ParseFieldErrorKind::OutOfRange => {
+                write!(f, "number is not less than the field modulus")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for ParseFieldError {}
+
+/// Parses a 256-bit integer from a decimal string, or a hexadecimal string with
+/// a `0x` prefix, returning it as 32 little-endian bytes.
+fn parse_u256(s: &str) -> Result<[u8; 32], ParseFieldError> {
+    let (digits, radix) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(digits) => (digits, 16),
+        None => (s, 10),
+    };
+    if digits.is_empty() {
+        return Err(ParseFieldError::new(ParseFieldErrorKind::Empty));
+    }
+
+    let mut limbs = [0u64; 4];
+    for c in digits.chars() {
+        let digit = c
+            .to_digit(radix)
+            .ok_or(ParseFieldError::new(ParseFieldErrorKind::InvalidDigit))?;
+
+        // limbs = limbs * radix + digit
+        let mut carry = digit as u64;
+        for limb in limbs.iter_mut() {
+            let (r, c) = mac(0, *limb, radix as u64, carry);
+            *limb = r;
+            carry = c;
+        }
+        if carry != 0 {
+            return Err(ParseFieldError::out_of_range());
+        }
+    }
+
+    let mut bytes = [0u8; 32];
+    for (chunk, limb) in bytes.chunks_mut(8).zip(limbs.iter()) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    Ok(bytes)
+}
+
+/// Formats 32 bytes as hexadecimal, in the order given. The `0x` prefix is
+/// included if the alternate flag (`#`) is set.
+pub(crate) fn fmt_hex(bytes: &[u8; 32], upper: bool, f: &mut fmt::Formatter) -> fmt::Result {
+    let digits: &[u8; 16] = if upper {
+        b"0123456789ABCDEF"
+    } else {
+        b"0123456789abcdef"
+    };
+
+    let mut buf = [0u8; 64];
+    for (chunk, b) in buf.chunks_mut(2).zip(bytes.iter()) {
+        chunk[0] = digits[(b >> 4) as usize];
+        chunk[1] = digits[(b & 0xf) as usize];
+    }
+
+    f.pad_integral(true, "0x", core::str::from_utf8(&buf).unwrap())
+}
+
+/// Formats a 256-bit integer, given as 32 little-endian bytes, in decimal.
+fn fmt_decimal(bytes: &[u8; 32], f: &mut fmt::Formatter) -> fmt::Result {
+    // The largest power of ten that fits in a u32, so that the division below
+    // only needs 64-bit arithmetic.
+    const TEN_POW_9: u64 = 1_000_000_000;
+
+    let mut limbs = [0u32; 8];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(4)) {
+        *limb = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    // 2^256 < 10^78, so 78 digits suffice.
+    let mut buf = [0u8; 78];
+    let mut pos = buf.len();
+    loop {
+        // Divide by 10^9, keeping the remainder.
+        let mut rem = 0u64;
+        for limb in limbs.iter_mut().rev() {
+            let cur = (rem << 32) | (*limb as u64);
+            *limb = (cur / TEN_POW_9) as u32;
+            rem = cur % TEN_POW_9;
+        }
+
+        // Emit the remainder's digits, padding with zeroes unless this is the
+        // most significant group.
+        let last = limbs == [0; 8];
+        for _ in 0..9 {
+            pos -= 1;
+            buf[pos] = b'0' + (rem % 10) as u8;
+            rem /= 10;
+            if last && rem == 0 {
+                break;
+            }
+        }
+        if last {
+            break;
+        }
+    }
+
+    f.pad_integral(true, "", core::str::from_utf8(&buf[pos..]).unwrap())
+}
+
+// The Pallas base field is the Vesta scalar field and vice versa. Since p < q < 2p,
+// every canonical element of Fp is also canonical in Fq, while an element of Fq is
+// canonical in Fp unless it lies in [p, q).
+
+impl Fq {
+    /// Converts an element of `Fp` into the element of `Fq` with the same canonical
+    /// integer value. This always succeeds, as the modulus of `Fp` is smaller.
+    pub fn from_fp(a: Fp) -> Fq {
+        Fq::from_repr(a.to_repr()).unwrap()
+    }
+}
+
+impl Fp {
+    /// Attempts to convert an element of `Fq` into the element of `Fp` with the same
+    /// canonical integer value, failing if that value is not less than the modulus of
+    /// `Fp`.
+    pub fn from_fq(a: Fq) -> CtOption<Fp> {
+        Fp::from_repr(a.to_repr())
+    }
+
+    /// Converts an element of `Fq` into an element of `Fp` by reducing its canonical
+    /// integer value modulo the modulus of `Fp`.
+    pub fn from_fq_reduced(a: Fq) -> Fp {
+        Fp::from_bytes_mod_order(&a.to_repr())
+    }
+}
+
+impl From<Fp> for Fq {
+    fn from(a: Fp) -> Fq {
+        Fq::from_fp(a)
+    }
+}
+
 /// Converts 64-bit little-endian limbs to 32-bit little endian limbs.
 #[cfg(feature = "gpu")]
 fn u64_to_u32(limbs: &[u64]) -> alloc::vec::Vec<u32> {
@@ -37,3 +212,25 @@ fn test_u64_to_u32() {
 
     assert_eq!(u64_le_bytes, u32_le_bytes);
 }
+
+#[test]
+fn test_cross_field_conversions() {
+    let p_minus_one = -Fp::one();
+    let q_minus_one = -Fq::one();
+
+    assert_eq!(Fq::from_fp(Fp::zero()), Fq::zero());
+    assert_eq!(Fq::from(Fp::from(5)), Fq::from(5));
+    assert_eq!(Fq::from_fp(p_minus_one).to_repr(), p_minus_one.to_repr());
+    assert_eq!(Fp::from_fq(Fq::from_fp(p_minus_one)).unwrap(), p_minus_one);
+
+    // p (as an element of Fq) is not canonical in Fp.
+    let p = Fq::from_fp(p_minus_one) + Fq::one();
+    assert!(bool::from(Fp::from_fq(p).is_none()));
+    assert_eq!(Fp::from_fq_reduced(p), Fp::zero());
+    assert!(bool::from(Fp::from_fq(q_minus_one).is_none()));
+    assert_eq!(
+        Fp::from_fq_reduced(q_minus_one),
+        Fp::from_fq(q_minus_one - p).unwrap()
+    );
+    assert_eq!(Fp::from_fq_reduced(Fq::from(7)), Fp::from(7));
+}
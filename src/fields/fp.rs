@@ -2,18 +2,31 @@ use core::fmt;
 use core::ops::{Add, Mul, Neg, Sub};
 
 use ff::{Field, FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
-use rand::RngCore;
-use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
-
-#[cfg(feature = "sqrt-table")]
+use rand::distributions::{Distribution, Standard};
+use rand::{Rng, RngCore};
+use subtle::{
+    Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater, ConstantTimeLess,
+    CtOption,
+};
+
+#[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
 use lazy_static::lazy_static;
 
 #[cfg(feature = "bits")]
 use ff::{FieldBits, PrimeFieldBits};
 
-use crate::arithmetic::{adc, mac, sbb, SqrtTableHelpers};
+use crate::arithmetic::{adc, invert_vartime_limbs, mac, mul_by_u64_limbs, sbb, SqrtTableHelpers};
+
+#[cfg(feature = "limbs-32")]
+use crate::arithmetic::mont_mul_32;
+
+#[cfg(all(feature = "mul-cios", not(feature = "limbs-32")))]
+use crate::arithmetic::mont_mul_cios;
 
-#[cfg(feature = "sqrt-table")]
+#[cfg(all(feature = "asm", target_arch = "x86_64"))]
+use crate::arithmetic::mont_mul_asm;
+
+#[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
 use crate::arithmetic::SqrtTables;
 
 /// This represents an element of $\mathbb{F}_p$ where
@@ -21,6 +34,11 @@ use crate::arithmetic::SqrtTables;
 /// `p = 0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001`
 ///
 /// is the base field of the Pallas curve.
+///
+/// The arithmetic methods `Fp::add`, `Fp::sub`, `Fp::mul`, `Fp::neg`,
+/// `Fp::double`, `Fp::square` and `Fp::from_raw` are `const fn`, so they can
+/// be used to compute constants at compile time. Call them on references
+/// (e.g. `(&a).add(&b)`) so that they are not shadowed by the `core::ops` traits.
 // The internal representation of this type is four 64-bit unsigned
 // integers in little-endian order. `Fp` values are always in
 // Montgomery form; i.e., Fp(a) = aR mod p, with R = 2^256.
@@ -39,6 +57,96 @@ impl fmt::Debug for Fp {
     }
 }
 
+impl fmt::LowerHex for Fp {
+    /// Formats this element as its canonical integer value in lowercase
+    /// hexadecimal, with all 64 digits (the same digits as [`fmt::Debug`]).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tmp = self.to_repr();
+        tmp.reverse();
+        super::fmt_hex(&tmp, false, f)
+    }
+}
+
+impl fmt::UpperHex for Fp {
+    /// Formats this element as its canonical integer value in uppercase
+    /// hexadecimal, with all 64 digits.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tmp = self.to_repr();
+        tmp.reverse();
+        super::fmt_hex(&tmp, true, f)
+    }
+}
+
+impl fmt::Display for Fp {
+    /// Formats this element as its canonical integer value in decimal.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        super::fmt_decimal(&self.to_repr(), f)
+    }
+}
+
+impl core::str::FromStr for Fp {
+    type Err = super::ParseFieldError;
+
+    /// Parses a field element from its canonical integer value, written either
+    /// in decimal or in hexadecimal with a `0x` prefix. Values that are not less
+    /// than the modulus are rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let repr = super::parse_u256(s)?;
+        Option::from(Fp::from_repr(repr)).ok_or(super::ParseFieldError::out_of_range())
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Fp {
+    type Error = crate::DecodeError;
+
+    /// Decodes a field element from its 32-byte canonical little-endian
+    /// encoding, as produced by [`PrimeField::to_repr`].
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        Fp::try_from_repr(crate::error::to_array(bytes)?)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl zeroize::DefaultIsZeroes for Fp {}
+
+// `Fp` is `repr(transparent)` over `[u64; 4]`, so it has no padding, and the
+// all-zero bit pattern is the zero element.
+#[cfg(feature = "bytemuck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+#[allow(unsafe_code)]
+unsafe impl bytemuck::Zeroable for Fp {}
+
+#[cfg(feature = "bytemuck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+#[allow(unsafe_code)]
+unsafe impl bytemuck::NoUninit for Fp {}
+
+// `Fp` does not implement `Pod`, as only limbs that are less than the modulus
+// form a valid element. Casting from bytes instead checks each element.
+#[cfg(feature = "bytemuck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+#[allow(unsafe_code)]
+unsafe impl bytemuck::CheckedBitPattern for Fp {
+    type Bits = [u64; 4];
+
+    fn is_valid_bit_pattern(bits: &[u64; 4]) -> bool {
+        // Check that bits < MODULUS by computing bits - MODULUS.
+        let (_, borrow) = sbb(bits[0], MODULUS.0[0], 0);
+        let (_, borrow) = sbb(bits[1], MODULUS.0[1], borrow);
+        let (_, borrow) = sbb(bits[2], MODULUS.0[2], borrow);
+        let (_, borrow) = sbb(bits[3], MODULUS.0[3], borrow);
+        borrow != 0
+    }
+}
+
+impl Distribution<Fp> for Standard {
+    /// Samples a uniformly random field element, as [`Field::random`] does.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Fp {
+        Fp::random(rng)
+    }
+}
+
 impl From<bool> for Fp {
     fn from(bit: bool) -> Fp {
         if bit {
@@ -71,6 +179,31 @@ impl PartialEq for Fp {
     }
 }
 
+impl ConstantTimeGreater for Fp {
+    /// Compares the canonical integer values of the two elements in constant
+    /// time, consistently with [`Ord`].
+    fn ct_gt(&self, other: &Self) -> Choice {
+        let a = Fp::montgomery_reduce(self.0[0], self.0[1], self.0[2], self.0[3], 0, 0, 0, 0);
+        let b = Fp::montgomery_reduce(other.0[0], other.0[1], other.0[2], other.0[3], 0, 0, 0, 0);
+
+        // a > b if and only if b - a underflows.
+        let (_, borrow) = sbb(b.0[0], a.0[0], 0);
+        let (_, borrow) = sbb(b.0[1], a.0[1], borrow);
+        let (_, borrow) = sbb(b.0[2], a.0[2], borrow);
+        let (_, borrow) = sbb(b.0[3], a.0[3], borrow);
+
+        Choice::from((borrow as u8) & 1)
+    }
+}
+
+impl ConstantTimeLess for Fp {}
+
+impl core::hash::Hash for Fp {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.to_repr().hash(state);
+    }
+}
+
 impl core::cmp::Ord for Fp {
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         let left = self.to_repr();
@@ -166,7 +299,20 @@ impl<'a, 'b> Mul<&'b Fp> for &'a Fp {
 
     #[inline]
     fn mul(self, rhs: &'b Fp) -> Fp {
-        self.mul(rhs)
+        #[cfg(feature = "custom-backend")]
+        if let Some(res) = crate::backend::fp_mul(self, rhs) {
+            return res;
+        }
+
+        #[cfg(all(feature = "asm", target_arch = "x86_64"))]
+        {
+            (&Fp(mont_mul_asm(&self.0, &rhs.0, &MODULUS.0, INV))).sub(&MODULUS)
+        }
+
+        #[cfg(not(all(feature = "asm", target_arch = "x86_64")))]
+        {
+            self.mul(rhs)
+        }
     }
 }
 
@@ -233,6 +379,27 @@ const ROOT_OF_UNITY: Fp = Fp::from_raw([
     0x2bce74deac30ebda,
 ]);
 
+/// ROOT_OF_UNITY^-1
+const ROOT_OF_UNITY_INV: Fp = Fp::from_raw([
+    0xf0b87c7db2ce91f6,
+    0x84a0a1d8859f066f,
+    0xb4ed8e647196dad1,
+    0x2cd5282c53116b5c,
+]);
+
+/// Returns `[root^(2^S), ..., root^2, root]`. If `root` is a primitive `2^S`-th root
+/// of unity, entry `k` of the result is a primitive `2^k`-th root of unity.
+const fn roots_of_unity(root: Fp) -> [Fp; S as usize + 1] {
+    let mut roots = [Fp::one(); S as usize + 1];
+    let mut i = S as usize;
+    roots[i] = root;
+    while i > 0 {
+        roots[i - 1] = roots[i].square();
+        i -= 1;
+    }
+    roots
+}
+
 /// GENERATOR^{2^s} where t * 2^s + 1 = p
 /// with t odd. In other words, this
 /// is a t root of unity.
@@ -244,7 +411,7 @@ const DELTA: Fp = Fp::from_raw([
 ]);
 
 /// `(t - 1) // 2` where t * 2^s + 1 = p with t odd.
-#[cfg(any(test, not(feature = "sqrt-table")))]
+#[cfg(test)]
 const T_MINUS1_OVER2: [u64; 4] = [
     0x04a6_7c8d_cc96_9876,
     0x0000_0000_1123_4c7e,
@@ -260,6 +427,29 @@ impl Default for Fp {
 }
 
 impl Fp {
+    /// The field modulus `p`, as little-endian 64-bit limbs.
+    ///
+    /// [`PrimeField::MODULUS`] provides the same value as a hex string, while the
+    /// 2-adicity `S` and the bit length are available as [`PrimeField::S`] and
+    /// [`PrimeField::NUM_BITS`].
+    pub const MODULUS_LIMBS: [u64; 4] = MODULUS.0;
+
+    /// The odd integer `t` such that `p - 1 = t * 2^S`, as little-endian 64-bit limbs.
+    pub const T: [u64; 4] = [
+        0x094c_f91b_992d_30ed,
+        0x0000_0000_2246_98fc,
+        0x0000_0000_0000_0000,
+        0x0000_0000_4000_0000,
+    ];
+
+    /// `ROOTS_OF_UNITY[k]` is a primitive `2^k`-th root of unity, for `0 <= k <= S`
+    /// where `S = 32` is the 2-adicity of the field.
+    /// In particular, `ROOTS_OF_UNITY[S]` is [`PrimeField::ROOT_OF_UNITY`].
+    pub const ROOTS_OF_UNITY: [Fp; S as usize + 1] = roots_of_unity(ROOT_OF_UNITY);
+
+    /// `ROOTS_OF_UNITY_INV[k]` is the inverse of `ROOTS_OF_UNITY[k]`, for `0 <= k <= S`.
+    pub const ROOTS_OF_UNITY_INV: [Fp; S as usize + 1] = roots_of_unity(ROOT_OF_UNITY_INV);
+
     /// Returns zero, the additive identity.
     #[inline]
     pub const fn zero() -> Fp {
@@ -275,11 +465,103 @@ impl Fp {
     /// Doubles this field element.
     #[inline]
     pub const fn double(&self) -> Fp {
-        // TODO: This can be achieved more efficiently with a bitshift.
-        self.add(self)
+        // The modulus is less than 2^255, so 2 * self fits in four limbs.
+        let d = Fp([
+            self.0[0] << 1,
+            (self.0[1] << 1) | (self.0[0] >> 63),
+            (self.0[2] << 1) | (self.0[1] >> 63),
+            (self.0[3] << 1) | (self.0[2] >> 63),
+        ]);
+
+        // Attempt to subtract the modulus, to ensure the value
+        // is smaller than the modulus.
+        (&d).sub(&MODULUS)
+    }
+
+    /// Returns `self / 2`.
+    ///
+    /// This is computed with a shift, after adding the modulus if needed to make the
+    /// value even, rather than by multiplying by [`PrimeField::TWO_INV`].
+    #[inline]
+    pub const fn halve(&self) -> Fp {
+        // mask = 0xfff...fff if self is odd, and 0x000...000 otherwise. Since the
+        // modulus is odd and less than 2^255, self + modulus is even and fits in
+        // four limbs.
+        let mask = (self.0[0] & 1).wrapping_neg();
+        let (d0, carry) = adc(self.0[0], MODULUS.0[0] & mask, 0);
+        let (d1, carry) = adc(self.0[1], MODULUS.0[1] & mask, carry);
+        let (d2, carry) = adc(self.0[2], MODULUS.0[2] & mask, carry);
+        let (d3, _) = adc(self.0[3], MODULUS.0[3] & mask, carry);
+
+        Fp([
+            (d0 >> 1) | (d1 << 63),
+            (d1 >> 1) | (d2 << 63),
+            (d2 >> 1) | (d3 << 63),
+            d3 >> 1,
+        ])
+    }
+
+    /// Returns `self / 2^k`.
+    ///
+    /// This runs in time linear in `k`, which is not treated as secret.
+    pub const fn div_by_pow_2(&self, k: u32) -> Fp {
+        let mut res = *self;
+        let mut i = 0;
+        while i < k {
+            res = res.halve();
+            i += 1;
+        }
+        res
     }
 
-    fn from_u512(limbs: [u64; 8]) -> Fp {
+    /// Doubles this element in place.
+    #[inline]
+    pub fn double_in_place(&mut self) {
+        *self = self.double();
+    }
+
+    /// Converts a little-endian integer of any length into an element of `Fp` by
+    /// reducing it modulo the field modulus.
+    ///
+    /// Reducing an input much wider than the modulus (for example, 64 bytes of a
+    /// hash output) gives a close to uniform result.
+    pub fn from_bytes_mod_order(bytes: &[u8]) -> Fp {
+        // Horner's rule over 512-bit digits, starting from the most significant
+        // (possibly partial) digit. R3 is the Montgomery form of 2^512.
+        let mut acc = Fp::zero();
+        for chunk in bytes.chunks(64).rev() {
+            let mut digit = [0u8; 64];
+            digit[..chunk.len()].copy_from_slice(chunk);
+            acc = acc * R3 + Fp::from_uniform_bytes(&digit);
+        }
+        acc
+    }
+
+    /// Returns `3 * self`.
+    #[inline]
+    pub const fn triple(&self) -> Fp {
+        (&self.double()).add(self)
+    }
+
+    /// Returns `self * k`.
+    ///
+    /// This is cheaper than converting `k` into a field element and performing a
+    /// full multiplication.
+    #[inline]
+    pub const fn mul_by_u64(&self, k: u64) -> Fp {
+        (&Fp(mul_by_u64_limbs(&self.0, k, &MODULUS.0))).sub(&MODULUS)
+    }
+
+    /// Returns `self * 3b`, where `b = 5` is the constant term of the curve
+    /// equation `y^2 = x^3 + 5` shared by Pallas and Vesta.
+    #[inline]
+    pub const fn mul_by_3b(&self) -> Fp {
+        self.mul_by_u64(15)
+    }
+
+    /// Converts a 512-bit little-endian integer, given as eight 64-bit limbs, into
+    /// an element of `Fp` by reducing it modulo the field modulus.
+    pub fn from_u512(limbs: [u64; 8]) -> Fp {
         // We reduce an arbitrary 512-bit number by decomposing it into two 256-bit digits
         // with the higher bits multiplied by 2^256. Thus, we perform two reductions
         //
@@ -295,8 +577,9 @@ impl Fp {
         // constant `R2` or `R3`.
         let d0 = Fp([limbs[0], limbs[1], limbs[2], limbs[3]]);
         let d1 = Fp([limbs[4], limbs[5], limbs[6], limbs[7]]);
-        // Convert to Montgomery form
-        d0 * R2 + d1 * R3
+        // Convert to Montgomery form. We use the `const fn` multiplication here
+        // because, unlike the `asm` backend, it accepts unreduced inputs.
+        (&d0).mul(&R2) + (&d1).mul(&R3)
     }
 
     /// Converts from an integer represented in little endian
@@ -305,36 +588,69 @@ impl Fp {
         (&Fp(val)).mul(&R2)
     }
 
+    /// Attempts to convert a little-endian byte representation of a scalar into
+    /// a `Fp`, as [`PrimeField::from_repr`] does, but returns a
+    /// [`DecodeError`](crate::DecodeError) on failure.
+    pub fn try_from_repr(repr: [u8; 32]) -> Result<Self, crate::DecodeError> {
+        Option::from(Fp::from_repr(repr)).ok_or(crate::DecodeError::NonCanonical)
+    }
+
+    /// Converts a signed integer into its (congruent) `Fp` representation, mapping
+    /// negative values `v` to `-|v|`.
+    ///
+    /// Unsigned integers can be converted with `From<u64>` and
+    /// [`PrimeField::from_u128`].
+    pub const fn from_i64(val: i64) -> Self {
+        let abs = Fp::from_raw([val.unsigned_abs(), 0, 0, 0]);
+        let neg = (&abs).neg();
+        // All ones if `val` is negative, all zeroes otherwise.
+        let mask = (val >> 63) as u64;
+        Fp([
+            (abs.0[0] & !mask) | (neg.0[0] & mask),
+            (abs.0[1] & !mask) | (neg.0[1] & mask),
+            (abs.0[2] & !mask) | (neg.0[2] & mask),
+            (abs.0[3] & !mask) | (neg.0[3] & mask),
+        ])
+    }
+
     /// Squares this element.
     #[cfg_attr(not(feature = "uninline-portable"), inline)]
     pub const fn square(&self) -> Fp {
-        let (r1, carry) = mac(0, self.0[0], self.0[1], 0);
-        let (r2, carry) = mac(0, self.0[0], self.0[2], carry);
-        let (r3, r4) = mac(0, self.0[0], self.0[3], carry);
-
-        let (r3, carry) = mac(r3, self.0[1], self.0[2], 0);
-        let (r4, r5) = mac(r4, self.0[1], self.0[3], carry);
-
-        let (r5, r6) = mac(r5, self.0[2], self.0[3], 0);
-
-        let r7 = r6 >> 63;
-        let r6 = (r6 << 1) | (r5 >> 63);
-        let r5 = (r5 << 1) | (r4 >> 63);
-        let r4 = (r4 << 1) | (r3 >> 63);
-        let r3 = (r3 << 1) | (r2 >> 63);
-        let r2 = (r2 << 1) | (r1 >> 63);
-        let r1 = r1 << 1;
-
-        let (r0, carry) = mac(0, self.0[0], self.0[0], 0);
-        let (r1, carry) = adc(0, r1, carry);
-        let (r2, carry) = mac(r2, self.0[1], self.0[1], carry);
-        let (r3, carry) = adc(0, r3, carry);
-        let (r4, carry) = mac(r4, self.0[2], self.0[2], carry);
-        let (r5, carry) = adc(0, r5, carry);
-        let (r6, carry) = mac(r6, self.0[3], self.0[3], carry);
-        let (r7, _) = adc(0, r7, carry);
+        #[cfg(feature = "limbs-32")]
+        {
+            self.mul(self)
+        }
 
-        Fp::montgomery_reduce(r0, r1, r2, r3, r4, r5, r6, r7)
+        #[cfg(not(feature = "limbs-32"))]
+        {
+            let (r1, carry) = mac(0, self.0[0], self.0[1], 0);
+            let (r2, carry) = mac(0, self.0[0], self.0[2], carry);
+            let (r3, r4) = mac(0, self.0[0], self.0[3], carry);
+
+            let (r3, carry) = mac(r3, self.0[1], self.0[2], 0);
+            let (r4, r5) = mac(r4, self.0[1], self.0[3], carry);
+
+            let (r5, r6) = mac(r5, self.0[2], self.0[3], 0);
+
+            let r7 = r6 >> 63;
+            let r6 = (r6 << 1) | (r5 >> 63);
+            let r5 = (r5 << 1) | (r4 >> 63);
+            let r4 = (r4 << 1) | (r3 >> 63);
+            let r3 = (r3 << 1) | (r2 >> 63);
+            let r2 = (r2 << 1) | (r1 >> 63);
+            let r1 = r1 << 1;
+
+            let (r0, carry) = mac(0, self.0[0], self.0[0], 0);
+            let (r1, carry) = adc(0, r1, carry);
+            let (r2, carry) = mac(r2, self.0[1], self.0[1], carry);
+            let (r3, carry) = adc(0, r3, carry);
+            let (r4, carry) = mac(r4, self.0[2], self.0[2], carry);
+            let (r5, carry) = adc(0, r5, carry);
+            let (r6, carry) = mac(r6, self.0[3], self.0[3], carry);
+            let (r7, _) = adc(0, r7, carry);
+
+            Fp::montgomery_reduce(r0, r1, r2, r3, r4, r5, r6, r7)
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -388,29 +704,42 @@ impl Fp {
     /// Multiplies `rhs` by `self`, returning the result.
     #[cfg_attr(not(feature = "uninline-portable"), inline)]
     pub const fn mul(&self, rhs: &Self) -> Self {
-        // Schoolbook multiplication
+        #[cfg(feature = "limbs-32")]
+        {
+            (&Fp(mont_mul_32(&self.0, &rhs.0, &MODULUS.0, INV))).sub(&MODULUS)
+        }
 
-        let (r0, carry) = mac(0, self.0[0], rhs.0[0], 0);
-        let (r1, carry) = mac(0, self.0[0], rhs.0[1], carry);
-        let (r2, carry) = mac(0, self.0[0], rhs.0[2], carry);
-        let (r3, r4) = mac(0, self.0[0], rhs.0[3], carry);
+        #[cfg(all(feature = "mul-cios", not(feature = "limbs-32")))]
+        {
+            (&Fp(mont_mul_cios(&self.0, &rhs.0, &MODULUS.0, INV))).sub(&MODULUS)
+        }
 
-        let (r1, carry) = mac(r1, self.0[1], rhs.0[0], 0);
-        let (r2, carry) = mac(r2, self.0[1], rhs.0[1], carry);
-        let (r3, carry) = mac(r3, self.0[1], rhs.0[2], carry);
-        let (r4, r5) = mac(r4, self.0[1], rhs.0[3], carry);
+        #[cfg(not(any(feature = "limbs-32", feature = "mul-cios")))]
+        {
+            // Schoolbook multiplication
 
-        let (r2, carry) = mac(r2, self.0[2], rhs.0[0], 0);
-        let (r3, carry) = mac(r3, self.0[2], rhs.0[1], carry);
-        let (r4, carry) = mac(r4, self.0[2], rhs.0[2], carry);
-        let (r5, r6) = mac(r5, self.0[2], rhs.0[3], carry);
+            let (r0, carry) = mac(0, self.0[0], rhs.0[0], 0);
+            let (r1, carry) = mac(0, self.0[0], rhs.0[1], carry);
+            let (r2, carry) = mac(0, self.0[0], rhs.0[2], carry);
+            let (r3, r4) = mac(0, self.0[0], rhs.0[3], carry);
 
-        let (r3, carry) = mac(r3, self.0[3], rhs.0[0], 0);
-        let (r4, carry) = mac(r4, self.0[3], rhs.0[1], carry);
-        let (r5, carry) = mac(r5, self.0[3], rhs.0[2], carry);
-        let (r6, r7) = mac(r6, self.0[3], rhs.0[3], carry);
+            let (r1, carry) = mac(r1, self.0[1], rhs.0[0], 0);
+            let (r2, carry) = mac(r2, self.0[1], rhs.0[1], carry);
+            let (r3, carry) = mac(r3, self.0[1], rhs.0[2], carry);
+            let (r4, r5) = mac(r4, self.0[1], rhs.0[3], carry);
 
-        Fp::montgomery_reduce(r0, r1, r2, r3, r4, r5, r6, r7)
+            let (r2, carry) = mac(r2, self.0[2], rhs.0[0], 0);
+            let (r3, carry) = mac(r3, self.0[2], rhs.0[1], carry);
+            let (r4, carry) = mac(r4, self.0[2], rhs.0[2], carry);
+            let (r5, r6) = mac(r5, self.0[2], rhs.0[3], carry);
+
+            let (r3, carry) = mac(r3, self.0[3], rhs.0[0], 0);
+            let (r4, carry) = mac(r4, self.0[3], rhs.0[1], carry);
+            let (r5, carry) = mac(r5, self.0[3], rhs.0[2], carry);
+            let (r6, r7) = mac(r6, self.0[3], rhs.0[3], carry);
+
+            Fp::montgomery_reduce(r0, r1, r2, r3, r4, r5, r6, r7)
+        }
     }
 
     /// Subtracts `rhs` from `self`, returning the result.
@@ -461,6 +790,162 @@ impl Fp {
 
         Fp([d0 & mask, d1 & mask, d2 & mask, d3 & mask])
     }
+
+    /// Computes the multiplicative inverse of this element in variable time,
+    /// failing if the element is zero.
+    ///
+    /// This uses the binary extended Euclidean algorithm, which is much faster
+    /// than [`Field::invert`] but leaks information about `self` through timing.
+    /// It must only be used on public values (for example, in verifiers).
+    pub fn invert_vartime(&self) -> CtOption<Self> {
+        match invert_vartime_limbs(&self.0, &MODULUS.0) {
+            // `self.0` is aR, so its integer inverse is a^-1 R^-1; we multiply it
+            // by R^3 and reduce to obtain a^-1 R.
+            Some(inv) => CtOption::new(Fp(inv) * R3, Choice::from(1)),
+            None => CtOption::new(Fp::zero(), Choice::from(0)),
+        }
+    }
+
+    /// Converts this element into its canonical byte representation in
+    /// big-endian order; this is [`PrimeField::to_repr`] with the bytes reversed.
+    pub fn to_repr_be(&self) -> [u8; 32] {
+        let mut repr = self.to_repr();
+        repr.reverse();
+        repr
+    }
+
+    /// Attempts to convert a big-endian byte representation of a field element
+    /// into an element of `Fp`, failing if the input is not canonical (is not
+    /// smaller than the modulus).
+    pub fn from_repr_be(mut repr: [u8; 32]) -> CtOption<Self> {
+        repr.reverse();
+        Self::from_repr(repr)
+    }
+
+    /// Returns whether this element is a quadratic residue (a square) in the
+    /// field. Zero is treated as a square.
+    ///
+    /// This computes the Legendre symbol `self^((p - 1) / 2)`, and is cheaper
+    /// than computing the square root.
+    pub fn is_quadratic_residue(&self) -> Choice {
+        // (p - 1) / 2 = t * 2^(S - 1), and self^t = (self^((t - 1) / 2))^2 * self.
+        let mut legendre = self.pow_by_t_minus1_over2().square() * self;
+        for _ in 1..Self::S {
+            legendre = legendre.square();
+        }
+
+        // The Legendre symbol is -1 for non-squares, and 0 or 1 otherwise.
+        !legendre.ct_eq(&-Self::one())
+    }
+
+    /// Returns whether the canonical value of this element is in the "low half"
+    /// of the field, i.e. at most `(modulus - 1) / 2`.
+    ///
+    /// Exactly one of `a` and `-a` is in the low half, unless `a` is zero.
+    pub fn is_below_half(&self) -> Choice {
+        // (modulus - 1) / 2 = (modulus + 1) / 2 - 1
+        let half = Self::TWO_INV - Self::one();
+        !self.ct_gt(&half)
+    }
+
+    /// Returns whichever of this element and its negation is in the low half of
+    /// the field (see [`Self::is_below_half`]), in constant time.
+    ///
+    /// This gives a canonical representative of `±a`, as used for example to
+    /// make signature scalars non-malleable.
+    pub fn normalize_low_half(&self) -> Self {
+        Self::conditional_select(&-self, self, self.is_below_half())
+    }
+
+    /// Inverts every element of `v` in place, performing only a single field
+    /// inversion. Elements equal to zero are left unchanged.
+    ///
+    /// If the `zeroize` feature is enabled, the intermediate products are
+    /// zeroized before returning.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn batch_invert(v: &mut [Fp]) {
+        let mut prods = alloc::vec::Vec::with_capacity(v.len());
+        crate::arithmetic::batch_invert_with_scratch(v, &mut prods);
+
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(prods.as_mut_slice());
+    }
+
+    /// Returns the inner product `sum_i a[i] * b[i]`.
+    ///
+    /// The 512-bit products are accumulated without being reduced (see
+    /// [`FpUnreduced`]), and only the final sum is reduced, which is cheaper
+    /// than a multiplication and addition per term.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` and `b` have different lengths.
+    pub fn sum_of_products(a: &[Fp], b: &[Fp]) -> Fp {
+        assert_eq!(a.len(), b.len());
+
+        let mut acc = FpUnreduced::default();
+        for (a, b) in a.iter().zip(b.iter()) {
+            acc.mul_add_assign(a, b);
+        }
+        acc.reduce()
+    }
+}
+
+/// An accumulator for sums of products of [`Fp`] elements, which defers
+/// modular reduction until [`FpUnreduced::reduce`] is called.
+///
+/// Each product is added as a 512-bit integer to a 576-bit accumulator, so at
+/// least `2^64` products can be accumulated before it can overflow.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FpUnreduced([u64; 9]);
+
+impl FpUnreduced {
+    /// Adds `a * b` to the accumulator.
+    #[inline]
+    pub fn mul_add_assign(&mut self, a: &Fp, b: &Fp) {
+        // acc += a * b, as integers.
+        let mut t = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0;
+            for j in 0..4 {
+                let (r, c) = mac(t[i + j], a.0[i], b.0[j], carry);
+                t[i + j] = r;
+                carry = c;
+            }
+            t[i + 4] = carry;
+        }
+
+        let mut carry = 0;
+        for (acc, t) in self.0.iter_mut().zip(t.iter()) {
+            let (r, c) = adc(*acc, *t, carry);
+            *acc = r;
+            carry = c;
+        }
+        self.0[8] += carry;
+    }
+
+    /// Adds `a` to the accumulator.
+    #[inline]
+    pub fn add_assign(&mut self, a: &Fp) {
+        // Products are accumulated as (aR)(bR), so a is added as (aR)(R).
+        self.mul_add_assign(a, &R);
+    }
+
+    /// Reduces the accumulated sum to a field element.
+    pub fn reduce(&self) -> Fp {
+        let acc = &self.0;
+
+        // Each product is (aR)(bR), so the result in Montgomery form is acc * R^-1.
+        // Writing acc = lo + mid * R + hi * R^2, this is
+        //   lo * R^-1 + mid + hi * R
+        // where each term is computed with a single Montgomery multiplication.
+        // The `const fn` multiplication is used as it accepts unreduced inputs.
+        let lo = (&Fp([acc[0], acc[1], acc[2], acc[3]])).mul(&Fp([1, 0, 0, 0]));
+        let mid = (&Fp([acc[4], acc[5], acc[6], acc[7]])).mul(&R);
+        let hi = (&Fp([acc[8], 0, 0, 0])).mul(&R2);
+        (&(&lo).add(&mid)).add(&hi)
+    }
 }
 
 impl From<Fp> for [u8; 32] {
@@ -498,47 +983,89 @@ impl ff::Field for Fp {
 
     #[inline(always)]
     fn square(&self) -> Self {
-        self.square()
+        #[cfg(all(feature = "asm", target_arch = "x86_64"))]
+        {
+            self * self
+        }
+
+        #[cfg(not(all(feature = "asm", target_arch = "x86_64")))]
+        {
+            self.square()
+        }
     }
 
     fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
-        #[cfg(feature = "sqrt-table")]
+        #[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
         {
             FP_TABLES.sqrt_ratio(num, div)
         }
 
-        #[cfg(not(feature = "sqrt-table"))]
+        #[cfg(any(not(feature = "sqrt-table"), feature = "zkvm"))]
         ff::helpers::sqrt_ratio_generic(num, div)
     }
 
-    #[cfg(feature = "sqrt-table")]
+    #[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
     fn sqrt_alt(&self) -> (Choice, Self) {
         FP_TABLES.sqrt_alt(self)
     }
 
     /// Computes the square root of this element, if it exists.
     fn sqrt(&self) -> CtOption<Self> {
-        #[cfg(feature = "sqrt-table")]
+        #[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
         {
             let (is_square, res) = FP_TABLES.sqrt_alt(self);
             CtOption::new(res, is_square)
         }
 
-        #[cfg(not(feature = "sqrt-table"))]
-        ff::helpers::sqrt_tonelli_shanks(self, &T_MINUS1_OVER2)
+        #[cfg(any(not(feature = "sqrt-table"), feature = "zkvm"))]
+        crate::arithmetic::sqrt_tonelli_shanks(self)
     }
 
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
     fn invert(&self) -> CtOption<Self> {
-        let tmp = self.pow_vartime(&[
-            0x992d30ecffffffff,
-            0x224698fc094cf91b,
-            0x0,
-            0x4000000000000000,
-        ]);
+        // Computes self^(p - 2) using an addition chain, built from a sliding
+        // window of width 4 over the bits of p - 2.
+        let sqr = |x: Fp, i: u32| (0..i).fold(x, |x, _| x.square());
 
-        CtOption::new(tmp, !self.ct_eq(&Self::zero()))
+        let r10 = self.square();
+        let r11 = r10 * self;
+        let r101 = r11 * r10;
+        let r111 = r101 * r10;
+        let r1001 = r111 * r10;
+        let r1011 = r1001 * r10;
+        let r1101 = r1011 * r10;
+        let r1111 = r1101 * r10;
+
+        let mut acc = sqr(*self, 129) * self;
+        acc = sqr(acc, 7) * r1001;
+        acc = sqr(acc, 7) * r1101;
+        acc = sqr(acc, 4) * r11;
+        acc = sqr(acc, 7) * r1111;
+        acc = sqr(acc, 2) * r11;
+        acc = sqr(acc, 10) * r1001;
+        acc = sqr(acc, 5) * r1001;
+        acc = sqr(acc, 4) * r1001;
+        acc = sqr(acc, 4) * r1111;
+        acc = sqr(acc, 3) * self;
+        acc = sqr(acc, 7) * r1101;
+        acc = sqr(acc, 2) * r11;
+        acc = sqr(acc, 4) * r11;
+        acc = sqr(acc, 6) * r1001;
+        acc = sqr(acc, 5) * r1101;
+        acc = sqr(acc, 4) * r11;
+        acc = sqr(acc, 7) * r111;
+        acc = sqr(acc, 3) * r11;
+        acc = sqr(acc, 6) * r1111;
+        acc = sqr(acc, 4) * r1111;
+        acc = sqr(acc, 4) * r1111;
+        acc = sqr(acc, 4) * r1111;
+        acc = sqr(acc, 4) * r1111;
+        acc = sqr(acc, 4) * r1111;
+        acc = sqr(acc, 4) * r1111;
+        acc = sqr(acc, 4) * r1111;
+
+        CtOption::new(acc, !self.ct_eq(&Self::zero()))
     }
 
     fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
@@ -576,12 +1103,7 @@ impl ff::PrimeField for Fp {
     const MULTIPLICATIVE_GENERATOR: Self = GENERATOR;
     const S: u32 = S;
     const ROOT_OF_UNITY: Self = ROOT_OF_UNITY;
-    const ROOT_OF_UNITY_INV: Self = Fp::from_raw([
-        0xf0b87c7db2ce91f6,
-        0x84a0a1d8859f066f,
-        0xb4ed8e647196dad1,
-        0x2cd5282c53116b5c,
-    ]);
+    const ROOT_OF_UNITY_INV: Self = ROOT_OF_UNITY_INV;
     const DELTA: Self = DELTA;
 
     fn from_u128(v: u128) -> Self {
@@ -681,7 +1203,7 @@ impl PrimeFieldBits for Fp {
     }
 }
 
-#[cfg(feature = "sqrt-table")]
+#[cfg(all(feature = "sqrt-table", not(feature = "zkvm")))]
 lazy_static! {
     // The perfect hash parameters are found by `squareroottab.sage` in zcash/pasta.
     #[cfg_attr(docsrs, doc(cfg(feature = "sqrt-table")))]
@@ -754,6 +1276,23 @@ impl FromUniformBytes<64> for Fp {
     }
 }
 
+impl FromUniformBytes<48> for Fp {
+    /// Converts a 384-bit little endian integer into
+    /// a `Fp` by reducing by the modulus.
+    fn from_uniform_bytes(bytes: &[u8; 48]) -> Fp {
+        Fp::from_u512([
+            u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            u64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+            u64::from_le_bytes(bytes[32..40].try_into().unwrap()),
+            u64::from_le_bytes(bytes[40..48].try_into().unwrap()),
+            0,
+            0,
+        ])
+    }
+}
+
 #[cfg(feature = "gpu")]
 impl ec_gpu::GpuName for Fp {
     fn name() -> alloc::string::String {
@@ -810,6 +1349,37 @@ fn test_pow_by_t_minus1_over2() {
     assert!(v == ff::Field::pow_vartime(&Fp::TWO_INV, &T_MINUS1_OVER2));
 }
 
+#[test]
+fn test_get_lower_32() {
+    assert_eq!(Fp::zero().get_lower_32(), 0);
+    assert_eq!(Fp::one().get_lower_32(), 1);
+    assert_eq!(Fp::from(0x1234_5678_9abc_def0).get_lower_32(), 0x9abc_def0);
+    assert_eq!(Fp::TWO_INV.get_lower_32(), 0x8000_0001);
+    assert_eq!((-Fp::one()).get_lower_32(), 0);
+}
+
+#[test]
+fn test_is_quadratic_residue() {
+    assert!(bool::from(Fp::zero().is_quadratic_residue()));
+    assert!(bool::from(Fp::one().is_quadratic_residue()));
+    assert!(!bool::from(Fp::from(5).is_quadratic_residue()));
+    assert!(!bool::from(Fp::ROOT_OF_UNITY.is_quadratic_residue()));
+
+    // NB: TWO_INV is standing in as a "random" field element
+    let mut a = Fp::TWO_INV;
+    for _ in 0..100 {
+        assert!(bool::from(a.square().is_quadratic_residue()));
+        assert!(!bool::from(
+            (a.square() * Fp::ROOT_OF_UNITY).is_quadratic_residue()
+        ));
+        assert_eq!(
+            bool::from(a.is_quadratic_residue()),
+            bool::from(a.sqrt().is_some())
+        );
+        a = a.square() + Fp::one();
+    }
+}
+
 #[test]
 fn test_sqrt_ratio_and_alt() {
     // (true, sqrt(num/div)), if num and div are nonzero and num/div is a square in the field
@@ -856,6 +1426,24 @@ fn test_sqrt_ratio_and_alt() {
     assert!(v == expected);
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_invert() {
+    let mut v = [
+        Fp::TWO_INV,
+        Fp::zero(),
+        Fp::from(5),
+        Fp::one(),
+        -Fp::ROOT_OF_UNITY,
+    ];
+    let expected = v.map(|x| x.invert().unwrap_or(Fp::zero()));
+    Fp::batch_invert(&mut v);
+    assert_eq!(v, expected);
+
+    let mut empty: [Fp; 0] = [];
+    Fp::batch_invert(&mut empty);
+}
+
 #[test]
 fn test_zeta() {
     assert_eq!(
@@ -879,6 +1467,207 @@ fn test_root_of_unity() {
     );
 }
 
+#[test]
+fn test_invert_addition_chain() {
+    const P_MINUS_2: [u64; 4] = [
+        0x992d30ecffffffff,
+        0x224698fc094cf91b,
+        0x0,
+        0x4000000000000000,
+    ];
+
+    // NB: TWO_INV is standing in as a "random" field element
+    let mut a = Fp::TWO_INV;
+    for _ in 0..100 {
+        assert_eq!(a.invert().unwrap(), a.pow_vartime(&P_MINUS_2));
+        assert_eq!(a * a.invert().unwrap(), Fp::one());
+        a = a.square() + Fp::one();
+    }
+}
+
+#[cfg(feature = "asm")]
+#[test]
+fn test_asm_mul() {
+    // NB: TWO_INV is standing in as a "random" field element
+    let mut a = Fp::TWO_INV;
+    let mut b = -Fp::one();
+    for _ in 0..100 {
+        // The operator may use the assembly backend, while the inherent `const fn`
+        // is always portable.
+        assert_eq!(a * b, Fp::mul(&a, &b));
+        assert_eq!(ff::Field::square(&a), Fp::square(&a));
+        a = a.square() + b;
+        b = b.double() + Fp::one();
+    }
+}
+
+#[test]
+fn test_display() {
+    use std::string::ToString;
+
+    assert_eq!(Fp::zero().to_string(), "0");
+    assert_eq!(Fp::one().to_string(), "1");
+    assert_eq!(
+        Fp::from(10_000_000_000_000_000_000).to_string(),
+        "10000000000000000000"
+    );
+    assert_eq!(
+        Fp::from(u64::MAX).square().to_string(),
+        "340282366920938463426481119284349108225"
+    );
+    assert_eq!(
+        (-Fp::one()).to_string(),
+        "28948022309329048855892746252171976963363056481941560715954676764349967630336"
+    );
+    assert_eq!(format!("{:>5}", Fp::from(42)), "   42");
+}
+
+#[test]
+fn test_hex() {
+    let a = Fp::from(0xdead_beef);
+    assert_eq!(
+        format!("{:x}", a),
+        "00000000000000000000000000000000000000000000000000000000deadbeef"
+    );
+    assert_eq!(
+        format!("{:#X}", a),
+        "0x00000000000000000000000000000000000000000000000000000000DEADBEEF"
+    );
+    assert_eq!(format!("{:#x}", -a), format!("{:?}", -a));
+}
+
+#[test]
+fn test_from_str() {
+    use core::str::FromStr;
+
+    assert_eq!(Fp::from_str("0").unwrap(), Fp::zero());
+    assert_eq!(Fp::from_str("12345").unwrap(), Fp::from(12345));
+    assert_eq!(Fp::from_str("0xdeadBEEF").unwrap(), Fp::from(0xdead_beef));
+    assert_eq!(Fp::from_str("0X0001").unwrap(), Fp::one());
+    assert_eq!(
+        Fp::from_str("340282366920938463426481119284349108225").unwrap(),
+        Fp::from(u64::MAX).square()
+    );
+
+    // The canonical encodings round-trip.
+    let a = -Fp::from(10);
+    assert_eq!(Fp::from_str(&format!("{}", a)).unwrap(), a);
+    assert_eq!(Fp::from_str(&format!("{:?}", a)).unwrap(), a);
+
+    // p - 1 is accepted, but the modulus and larger values are rejected.
+    assert!(Fp::from_str(&format!("{:?}", -Fp::one())).is_ok());
+    assert!(
+        Fp::from_str("0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001").is_err()
+    );
+    assert!(Fp::from_str(&format!("0x1{}", "0".repeat(64))).is_err());
+
+    assert!(Fp::from_str("").is_err());
+    assert!(Fp::from_str("0x").is_err());
+    assert!(Fp::from_str("-1").is_err());
+    assert!(Fp::from_str("12a").is_err());
+    assert!(Fp::from_str(" 1").is_err());
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_zeroize() {
+    use zeroize::Zeroize;
+
+    let mut a = Fp::from(0x123456789abcdef);
+    a.zeroize();
+    assert!(bool::from(a.is_zero()));
+}
+
+#[test]
+fn test_distribution() {
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    let seed = [
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ];
+    let a: Fp = XorShiftRng::from_seed(seed).gen();
+    let b = Fp::random(XorShiftRng::from_seed(seed));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_from_uniform_bytes() {
+    let mut wide = [0u8; 64];
+    wide[0] = 1;
+    let mut narrow = [0u8; 48];
+    narrow[0] = 1;
+    assert_eq!(
+        <Fp as FromUniformBytes<64>>::from_uniform_bytes(&wide),
+        Fp::one()
+    );
+    assert_eq!(
+        <Fp as FromUniformBytes<48>>::from_uniform_bytes(&narrow),
+        Fp::one()
+    );
+
+    // 2^384 - 1 and 2^512 - 1
+    let two = Fp::from(2);
+    assert_eq!(
+        <Fp as FromUniformBytes<48>>::from_uniform_bytes(&[0xff; 48]),
+        two.pow_vartime(&[384]) - Fp::one()
+    );
+    assert_eq!(
+        <Fp as FromUniformBytes<64>>::from_uniform_bytes(&[0xff; 64]),
+        two.pow_vartime(&[512]) - Fp::one()
+    );
+}
+
+#[test]
+fn test_repr_be() {
+    let a = Fp::from(0x0102_0304_0506_0708);
+    let mut expected = [0u8; 32];
+    expected[24..].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(a.to_repr_be(), expected);
+    assert_eq!(Fp::from_repr_be(expected).unwrap(), a);
+
+    let a = -Fp::from(7);
+    assert_eq!(Fp::from_repr_be(a.to_repr_be()).unwrap(), a);
+
+    // The modulus itself is not canonical.
+    let mut modulus = (-Fp::one()).to_repr_be();
+    modulus[31] += 1;
+    assert!(bool::from(Fp::from_repr_be(modulus).is_none()));
+}
+
+#[test]
+fn test_const_arithmetic() {
+    // These must remain usable in `const` contexts, so that downstream crates can
+    // compute constants such as round constants at compile time. The methods are
+    // called on references so that they are not shadowed by the `core::ops` traits.
+    const TWO: Fp = Fp::one().double();
+    const FIVE: Fp = Fp::from_raw([5, 0, 0, 0]);
+    const SEVEN: Fp = (&FIVE).add(&TWO);
+    const MINUS_TEN: Fp = (&(&FIVE).mul(&TWO)).neg();
+    const THREE: Fp = (&FIVE).sub(&TWO);
+    const NINE: Fp = THREE.square();
+
+    assert_eq!(TWO, Fp::from(2));
+    assert_eq!(SEVEN, Fp::from(7));
+    assert_eq!(MINUS_TEN, -Fp::from(10));
+    assert_eq!(NINE, Fp::from(9));
+}
+
+#[test]
+fn test_invert_vartime() {
+    assert!(bool::from(Fp::zero().invert_vartime().is_none()));
+    assert_eq!(Fp::one().invert_vartime().unwrap(), Fp::one());
+    assert_eq!((-Fp::one()).invert_vartime().unwrap(), -Fp::one());
+
+    // NB: TWO_INV is standing in as a "random" field element
+    let mut a = Fp::TWO_INV;
+    for _ in 0..100 {
+        assert_eq!(a.invert_vartime().unwrap(), a.invert().unwrap());
+        a = a.square() + Fp::one();
+    }
+}
+
 #[test]
 fn test_inv_root_of_unity() {
     assert_eq!(Fp::ROOT_OF_UNITY_INV, Fp::ROOT_OF_UNITY.invert().unwrap());
@@ -936,3 +1725,381 @@ fn test_from_u512() {
         ])
     );
 }
+
+#[test]
+fn test_roots_of_unity() {
+    assert_eq!(Fp::ROOTS_OF_UNITY[0], Fp::one());
+    assert_eq!(Fp::ROOTS_OF_UNITY[1], -Fp::one());
+    assert_eq!(Fp::ROOTS_OF_UNITY[S as usize], Fp::ROOT_OF_UNITY);
+    assert_eq!(Fp::ROOTS_OF_UNITY_INV[S as usize], Fp::ROOT_OF_UNITY_INV);
+
+    for k in 0..=S as usize {
+        let w = Fp::ROOTS_OF_UNITY[k];
+        assert_eq!(w * Fp::ROOTS_OF_UNITY_INV[k], Fp::one());
+        assert_eq!(w.pow_vartime(&[1u64 << k]), Fp::one());
+        if k > 0 {
+            // w is primitive: w^(2^(k-1)) = -1.
+            assert_eq!(w.pow_vartime(&[1u64 << (k - 1)]), -Fp::one());
+        }
+    }
+}
+
+#[test]
+fn test_sum_of_products() {
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert_eq!(Fp::sum_of_products(&[], &[]), Fp::zero());
+
+    for n in [1, 2, 3, 4, 5, 17, 1000] {
+        let a: std::vec::Vec<Fp> = (0..n).map(|_| Fp::random(&mut rng)).collect();
+        let b: std::vec::Vec<Fp> = (0..n).map(|_| Fp::random(&mut rng)).collect();
+        let expected = a.iter().zip(b.iter()).map(|(a, b)| a * b).sum::<Fp>();
+        assert_eq!(Fp::sum_of_products(&a, &b), expected);
+    }
+
+    // The largest possible products, where every element is p - 1 in Montgomery form.
+    let max = Fp([MODULUS.0[0] - 1, MODULUS.0[1], MODULUS.0[2], MODULUS.0[3]]);
+    let a = vec![max; 1000];
+    assert_eq!(Fp::sum_of_products(&a, &a), max.square() * Fp::from(1000));
+}
+
+#[test]
+fn test_small_constant_mul() {
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    // p - 1 in Montgomery form, which has the largest limbs.
+    let max = Fp([MODULUS.0[0] - 1, MODULUS.0[1], MODULUS.0[2], MODULUS.0[3]]);
+    let elements = (0..100)
+        .map(|_| Fp::random(&mut rng))
+        .chain([Fp::zero(), Fp::one(), -Fp::one(), max])
+        .collect::<std::vec::Vec<_>>();
+
+    for a in elements {
+        assert_eq!(a.double(), a + a);
+        let mut b = a;
+        b.double_in_place();
+        assert_eq!(b, a + a);
+        assert_eq!(a.triple(), a + a + a);
+        assert_eq!(a.mul_by_3b(), a * Fp::from(15));
+
+        for k in [
+            0,
+            1,
+            2,
+            15,
+            0xffff_ffff,
+            u64::MAX - 1,
+            u64::MAX,
+            rng.next_u64(),
+        ] {
+            assert_eq!(a.mul_by_u64(k), a * Fp::from(k));
+        }
+    }
+}
+
+#[test]
+fn test_from_bytes_mod_order() {
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert_eq!(Fp::from_bytes_mod_order(&[]), Fp::zero());
+    assert_eq!(Fp::from_bytes_mod_order(&[7]), Fp::from(7));
+
+    // Canonical encodings, with any amount of zero padding, are unchanged.
+    let a = Fp::random(&mut rng);
+    let mut bytes = a.to_repr().to_vec();
+    for _ in 0..100 {
+        assert_eq!(Fp::from_bytes_mod_order(&bytes), a);
+        bytes.push(0);
+    }
+
+    // The modulus reduces to zero.
+    let mut modulus = (-Fp::one()).to_repr();
+    modulus[0] += 1;
+    assert_eq!(Fp::from_bytes_mod_order(&modulus), Fp::zero());
+
+    // Compare against byte-wise Horner's rule for various lengths.
+    for len in [1, 31, 32, 33, 63, 64, 65, 127, 128, 129, 200] {
+        let mut bytes = vec![0u8; len];
+        rng.fill_bytes(&mut bytes);
+        let expected = bytes.iter().rev().fold(Fp::zero(), |acc, b| {
+            acc * Fp::from(256) + Fp::from(*b as u64)
+        });
+        assert_eq!(Fp::from_bytes_mod_order(&bytes), expected);
+    }
+    let bytes = [0xffu8; 64];
+    assert_eq!(
+        Fp::from_bytes_mod_order(&bytes),
+        Fp::from_uniform_bytes(&bytes)
+    );
+
+    assert_eq!(Fp::from_u512([1, 0, 0, 0, 0, 0, 0, 0]), Fp::one());
+}
+
+#[test]
+fn test_halve() {
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert_eq!(Fp::zero().halve(), Fp::zero());
+    assert_eq!(Fp::one().halve(), Fp::TWO_INV);
+    assert_eq!(Fp::from(6).halve(), Fp::from(3));
+    assert_eq!((-Fp::one()).halve().double(), -Fp::one());
+
+    for _ in 0..100 {
+        let a = Fp::random(&mut rng);
+        assert_eq!(a.halve(), a * Fp::TWO_INV);
+        assert_eq!(a.halve().double(), a);
+        assert_eq!(a.div_by_pow_2(0), a);
+        assert_eq!(a.div_by_pow_2(5), a * Fp::from(32).invert().unwrap());
+        assert_eq!(
+            a.div_by_pow_2(100).mul_by_u64(1 << 50).mul_by_u64(1 << 50),
+            a
+        );
+    }
+}
+
+#[test]
+fn test_from_i64() {
+    const MINUS_SEVEN: Fp = Fp::from_i64(-7);
+
+    assert_eq!(MINUS_SEVEN, -Fp::from(7));
+    assert_eq!(Fp::from_i64(0), Fp::zero());
+    assert_eq!(Fp::from_i64(7), Fp::from(7));
+    assert_eq!(Fp::from_i64(-1), -Fp::one());
+    assert_eq!(Fp::from_i64(i64::MIN), -Fp::from(1 << 63));
+    assert_eq!(Fp::from_i64(i64::MAX), Fp::from(i64::MAX as u64));
+}
+
+#[test]
+fn test_sum_product() {
+    let v: [Fp; 4] = [Fp::from(2), Fp::from(3), -Fp::from(5), Fp::TWO_INV];
+
+    assert_eq!(v.iter().sum::<Fp>(), v[0] + v[1] + v[2] + v[3]);
+    assert_eq!(v.iter().copied().sum::<Fp>(), v.iter().sum::<Fp>());
+    assert_eq!(v.iter().product::<Fp>(), -Fp::from(15));
+    assert_eq!(v.iter().copied().product::<Fp>(), v.iter().product::<Fp>());
+    assert_eq!(core::iter::empty::<Fp>().sum::<Fp>(), Fp::zero());
+    assert_eq!(core::iter::empty::<&Fp>().product::<Fp>(), Fp::one());
+}
+
+#[test]
+fn test_try_from_bytes() {
+    use crate::DecodeError;
+
+    let a = -Fp::from(7);
+    assert_eq!(Fp::try_from(&a.to_repr()[..]), Ok(a));
+    assert_eq!(Fp::try_from(&[0u8; 32][..]), Ok(Fp::zero()));
+
+    assert_eq!(
+        Fp::try_from(&[0u8; 31][..]),
+        Err(DecodeError::InvalidLength {
+            expected: 32,
+            actual: 31
+        })
+    );
+    assert_eq!(
+        Fp::try_from(&[0u8; 33][..]),
+        Err(DecodeError::InvalidLength {
+            expected: 32,
+            actual: 33
+        })
+    );
+    assert_eq!(
+        Fp::try_from(&[0xff; 32][..]),
+        Err(DecodeError::NonCanonical)
+    );
+    assert_eq!(Fp::try_from_repr(a.to_repr()), Ok(a));
+    let mut modulus = [0u8; 32];
+    for (chunk, limb) in modulus.chunks_mut(8).zip(MODULUS.0.iter()) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    assert_eq!(Fp::try_from_repr(modulus), Err(DecodeError::NonCanonical));
+}
+
+#[test]
+fn test_ct_ordering() {
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    // Montgomery form does not preserve ordering, so compare with small values
+    // and with the extremes.
+    let one = Fp::one();
+    let max = -Fp::one();
+    assert!(bool::from(Fp::from(3).ct_gt(&Fp::from(2))));
+    assert!(bool::from(Fp::from(2).ct_lt(&Fp::from(3))));
+    assert!(bool::from(max.ct_gt(&one)));
+    assert!(bool::from(one.ct_gt(&Fp::zero())));
+    assert!(!bool::from(one.ct_gt(&one)));
+    assert!(!bool::from(one.ct_lt(&one)));
+
+    for _ in 0..100 {
+        let a = Fp::random(&mut rng);
+        let b = Fp::random(&mut rng);
+        assert_eq!(bool::from(a.ct_gt(&b)), a > b);
+        assert_eq!(bool::from(a.ct_lt(&b)), a < b);
+    }
+}
+
+#[test]
+fn test_low_half() {
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let half = Fp::TWO_INV - Fp::one();
+    assert!(bool::from(Fp::zero().is_below_half()));
+    assert!(bool::from(Fp::one().is_below_half()));
+    assert!(bool::from(half.is_below_half()));
+    assert!(!bool::from(Fp::TWO_INV.is_below_half()));
+    assert!(!bool::from((-Fp::one()).is_below_half()));
+
+    assert_eq!(Fp::zero().normalize_low_half(), Fp::zero());
+    assert_eq!((-Fp::one()).normalize_low_half(), Fp::one());
+    assert_eq!(Fp::TWO_INV.normalize_low_half(), half);
+
+    for _ in 0..100 {
+        let a = Fp::random(&mut rng);
+        assert_ne!(
+            bool::from(a.is_below_half()),
+            bool::from((-a).is_below_half())
+        );
+        let n = a.normalize_low_half();
+        assert!(bool::from(n.is_below_half()));
+        assert_eq!(n, (-a).normalize_low_half());
+        assert!(n == a || n == -a);
+    }
+}
+
+#[test]
+fn test_unreduced() {
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert_eq!(FpUnreduced::default().reduce(), Fp::zero());
+
+    let mut acc = FpUnreduced::default();
+    let mut expected = Fp::zero();
+    for _ in 0..100 {
+        let a = Fp::random(&mut rng);
+        let b = Fp::random(&mut rng);
+        let c = Fp::random(&mut rng);
+        acc.mul_add_assign(&a, &b);
+        acc.add_assign(&c);
+        expected += a * b + c;
+        assert_eq!(acc.reduce(), expected);
+    }
+
+    // The extreme values must not overflow the accumulator.
+    let max = -Fp::one();
+    let mut acc = FpUnreduced::default();
+    for _ in 0..1000 {
+        acc.mul_add_assign(&max, &max);
+    }
+    assert_eq!(acc.reduce(), Fp::from(1000));
+}
+
+#[test]
+fn test_hash() {
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::collections::HashSet;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let v: std::vec::Vec<Fp> = (0..100).map(|_| Fp::random(&mut rng)).collect();
+    let set: HashSet<Fp> = v.iter().chain(v.iter()).copied().collect();
+    assert_eq!(set.len(), v.len());
+    assert!(v.iter().all(|a| set.contains(a)));
+    assert!(!set.contains(&(Fp::one() + v[0])));
+}
+
+#[test]
+fn test_modulus_metadata() {
+    assert_eq!(Fp::MODULUS_LIMBS, MODULUS.0);
+    assert_eq!(
+        Fp::from_raw(Fp::MODULUS_LIMBS),
+        Fp::zero(),
+        "the modulus reduces to zero"
+    );
+
+    // t is odd and t * 2^S + 1 = modulus.
+    assert_eq!(Fp::T[0] & 1, 1);
+    let t_shifted = [
+        Fp::T[0] << S,
+        (Fp::T[1] << S) | (Fp::T[0] >> (64 - S)),
+        (Fp::T[2] << S) | (Fp::T[1] >> (64 - S)),
+        (Fp::T[3] << S) | (Fp::T[2] >> (64 - S)),
+    ];
+    assert_eq!(Fp::T[3] >> (64 - S), 0);
+    assert_eq!(
+        [t_shifted[0] + 1, t_shifted[1], t_shifted[2], t_shifted[3]],
+        Fp::MODULUS_LIMBS
+    );
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn test_bytemuck() {
+    use bytemuck::checked::{try_cast_slice, CheckedCastError};
+
+    let v = [Fp::zero(), Fp::one(), -Fp::one(), Fp::TWO_INV];
+    let bytes: &[u8] = bytemuck::cast_slice(&v);
+    assert_eq!(bytes.len(), 32 * v.len());
+    assert_eq!(&bytes[32..64], bytemuck::bytes_of(&Fp::one()));
+
+    let limbs: &[[u64; 4]] = bytemuck::cast_slice(&v);
+    assert_eq!(try_cast_slice::<[u64; 4], Fp>(limbs), Ok(&v[..]));
+    assert_eq!(<Fp as bytemuck::Zeroable>::zeroed(), Fp::zero());
+
+    // Limbs that are not less than the modulus are rejected.
+    let max = [MODULUS.0[0] - 1, MODULUS.0[1], MODULUS.0[2], MODULUS.0[3]];
+    assert_eq!(try_cast_slice::<[u64; 4], Fp>(&[max]).unwrap(), &[Fp(max)]);
+    assert_eq!(
+        try_cast_slice::<[u64; 4], Fp>(&[MODULUS.0]),
+        Err(CheckedCastError::InvalidBitPattern)
+    );
+    assert_eq!(
+        try_cast_slice::<[u64; 4], Fp>(&[[u64::MAX; 4]]),
+        Err(CheckedCastError::InvalidBitPattern)
+    );
+}
@@ -0,0 +1,151 @@
+//! GLV decomposition of scalars for the curve endomorphism.
+//!
+//! On both curves, `endo(P) = (ZETA * x, y)` equals `[lambda] P`, where `lambda` is the
+//! `ZETA` of the scalar field. A scalar `k` is split into `k = k1 + k2 * lambda` with
+//! `|k1|, |k2| < 2^128`, so that `[k] P = [k1] P + [k2] endo(P)` can be computed with
+//! half the doublings.
+//!
+//! The decomposition follows Gallant, Lambert and Vanstone: with a short basis
+//! `(a1, b1), (a2, b2)` of the lattice `{(x, y) : x + y * lambda = 0 mod n}`,
+//!
+//! ```text
+//! c1 = round(b2 * k / n),  c2 = round(-b1 * k / n),
+//! k1 = k - c1 * a1 - c2 * a2,  k2 = -c1 * b1 - c2 * b2,
+//! ```
+//!
+//! where the divisions are computed as multiplications by `g = round(2^256 * b / n)`.
+
+use ff::PrimeField;
+use subtle::{Choice, ConditionallyNegatable};
+
+use crate::arithmetic::{adc, mac};
+use crate::fields::{Fp, Fq};
+
+/// Computes `round(k * g / 2^256)`, which must be less than `2^128`.
+fn mul_shift(k: &[u64; 4], g: &[u64; 3]) -> [u64; 4] {
+    let mut t = [0u64; 7];
+    for (i, g) in g.iter().enumerate() {
+        let mut carry = 0;
+        for (j, k) in k.iter().enumerate() {
+            let (lo, hi) = mac(t[i + j], *k, *g, carry);
+            t[i + j] = lo;
+            carry = hi;
+        }
+        t[i + 4] = carry;
+    }
+
+    // Round by adding 2^255.
+    let (_, carry) = adc(t[3], 1 << 63, 0);
+    let (r0, carry) = adc(t[4], 0, carry);
+    let (r1, _) = adc(t[5], 0, carry);
+    [r0, r1, 0, 0]
+}
+
+/// Returns the value of `k`, which must be less than `2^128`.
+pub(crate) fn to_u128<F: PrimeField<Repr = [u8; 32]>>(k: &F) -> u128 {
+    let repr = k.to_repr();
+    debug_assert!(repr[16..].iter().all(|b| *b == 0));
+    u128::from_le_bytes(repr[..16].try_into().unwrap())
+}
+
+macro_rules! impl_glv {
+    ($scalar:ident, $a1:expr, $minus_b1:expr, $a2:expr, $b2:expr, $g1:expr, $g2:expr) => {
+        impl $scalar {
+            /// Returns `(k1, k1_neg, k2, k2_neg)` such that
+            /// `self = ±k1 + ±k2 * ZETA`, where `k1, k2 < 2^128` and each sign is
+            /// negative if the corresponding `_neg` is set.
+            ///
+            /// This runs in constant time.
+            pub(crate) fn glv_decompose(&self) -> ($scalar, Choice, $scalar, Choice) {
+                const A1: $scalar = $scalar::from_raw($a1);
+                const MINUS_B1: $scalar = $scalar::from_raw($minus_b1);
+                const A2: $scalar = $scalar::from_raw($a2);
+                const B2: $scalar = $scalar::from_raw($b2);
+
+                let repr = self.to_repr();
+                let mut k = [0u64; 4];
+                for (limb, chunk) in k.iter_mut().zip(repr.chunks(8)) {
+                    *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+                }
+
+                let c1 = $scalar::from_raw(mul_shift(&k, &$g1));
+                let c2 = $scalar::from_raw(mul_shift(&k, &$g2));
+
+                let mut k1 = self - c1 * A1 - c2 * A2;
+                let mut k2 = c1 * MINUS_B1 - c2 * B2;
+
+                let k1_neg = !k1.is_below_half();
+                let k2_neg = !k2.is_below_half();
+                k1.conditional_negate(k1_neg);
+                k2.conditional_negate(k2_neg);
+
+                (k1, k1_neg, k2, k2_neg)
+            }
+        }
+    };
+}
+
+impl_glv!(
+    Fq,
+    [0x7fcae1c700000001, 0x49e69d1640f04915, 0, 0],
+    [0x8cb1279300000000, 0x49e69d1640a89953, 0, 0],
+    [0x8cb1279300000000, 0x49e69d1640a89953, 0, 0],
+    [0x0c7c095a00000001, 0x93cd3a2c8198e269, 0, 0],
+    [0x31f0256800000003, 0x4f34e8b2066389a4, 0x2],
+    [0x32c49e4bffffffff, 0x279a745902a2654e, 0x1]
+);
+
+impl_glv!(
+    Fp,
+    [0x7fcae1c700000000, 0x49e69d1640f04915, 0, 0],
+    [0x8cb1279300000001, 0x49e69d1640a89953, 0, 0],
+    [0x8cb1279300000001, 0x49e69d1640a89953, 0, 0],
+    [0x0c7c095a00000001, 0x93cd3a2c8198e269, 0, 0],
+    [0x31f0256800000003, 0x4f34e8b2066389a4, 0x2],
+    [0x32c49e4c00000003, 0x279a745902a2654e, 0x1]
+);
+
+#[cfg(test)]
+mod tests {
+    use ff::{Field, PrimeField, WithSmallOrderMulGroup};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    use crate::fields::{Fp, Fq};
+
+    macro_rules! test_decompose {
+        ($test:ident, $scalar:ident) => {
+            #[test]
+            fn $test() {
+                let mut rng = XorShiftRng::from_seed([
+                    0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54,
+                    0x06, 0xbc, 0xe5,
+                ]);
+
+                let edge_cases = [
+                    $scalar::zero(),
+                    $scalar::one(),
+                    -$scalar::one(),
+                    $scalar::ZETA,
+                    $scalar::ZETA - $scalar::one(),
+                    $scalar::TWO_INV,
+                ];
+                for k in edge_cases
+                    .into_iter()
+                    .chain((0..1000).map(|_| $scalar::random(&mut rng)))
+                {
+                    let (k1, k1_neg, k2, k2_neg) = k.glv_decompose();
+                    assert!(k1.to_repr()[16..].iter().all(|b| *b == 0));
+                    assert!(k2.to_repr()[16..].iter().all(|b| *b == 0));
+
+                    let k1 = if bool::from(k1_neg) { -k1 } else { k1 };
+                    let k2 = if bool::from(k2_neg) { -k2 } else { k2 };
+                    assert_eq!(k1 + k2 * $scalar::ZETA, k);
+                }
+            }
+        };
+    }
+
+    test_decompose!(test_decompose_fq, Fq);
+    test_decompose!(test_decompose_fp, Fp);
+}
@@ -0,0 +1,118 @@
+//! Conversions to and from the Pasta types exported by `halo2curves::pasta`.
+//!
+//! Field elements are converted through their canonical encodings, and points through
+//! their affine coordinates, so no point decompression is required.
+
+use ff::PrimeField;
+use group::{prime::PrimeCurveAffine, Curve};
+use halo2curves::{pasta, CurveAffine as _};
+
+use crate::{
+    arithmetic::{Coordinates, CurveAffine},
+    curves::{Ep, EpAffine, Eq, EqAffine},
+    fields::{Fp, Fq},
+};
+
+macro_rules! impl_field_conversions {
+    ($field:ident) => {
+        impl From<$field> for pasta::$field {
+            fn from(a: $field) -> pasta::$field {
+                pasta::$field::from_repr(a.to_repr()).unwrap()
+            }
+        }
+
+        impl From<pasta::$field> for $field {
+            fn from(a: pasta::$field) -> $field {
+                $field::from_repr(a.to_repr()).unwrap()
+            }
+        }
+    };
+}
+
+impl_field_conversions!(Fp);
+impl_field_conversions!(Fq);
+
+macro_rules! impl_point_conversions {
+    ($name:ident, $name_affine:ident) => {
+        impl From<$name_affine> for pasta::$name_affine {
+            fn from(p: $name_affine) -> pasta::$name_affine {
+                let coordinates: Option<Coordinates<$name_affine>> = p.coordinates().into();
+                coordinates
+                    .map(|c| {
+                        pasta::$name_affine::from_xy((*c.x()).into(), (*c.y()).into()).unwrap()
+                    })
+                    .unwrap_or_else(pasta::$name_affine::identity)
+            }
+        }
+
+        impl From<pasta::$name_affine> for $name_affine {
+            fn from(p: pasta::$name_affine) -> $name_affine {
+                let coordinates: Option<halo2curves::Coordinates<pasta::$name_affine>> =
+                    p.coordinates().into();
+                coordinates
+                    .map(|c| $name_affine::from_xy((*c.x()).into(), (*c.y()).into()).unwrap())
+                    .unwrap_or_else($name_affine::identity)
+            }
+        }
+
+        impl From<$name> for pasta::$name {
+            fn from(p: $name) -> pasta::$name {
+                pasta::$name_affine::from(p.to_affine()).into()
+            }
+        }
+
+        impl From<pasta::$name> for $name {
+            fn from(p: pasta::$name) -> $name {
+                $name_affine::from(p.to_affine()).into()
+            }
+        }
+    };
+}
+
+impl_point_conversions!(Ep, EpAffine);
+impl_point_conversions!(Eq, EqAffine);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ff::Field;
+    use group::{Group, GroupEncoding};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn halo2curves_roundtrip() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for _ in 0..100 {
+            let a = Fp::random(&mut rng);
+            assert_eq!(pasta::Fp::from(a).to_repr(), a.to_repr());
+            assert_eq!(Fp::from(pasta::Fp::from(a)), a);
+
+            let a = Fq::random(&mut rng);
+            assert_eq!(pasta::Fq::from(a).to_repr(), a.to_repr());
+            assert_eq!(Fq::from(pasta::Fq::from(a)), a);
+
+            let p = Ep::random(&mut rng);
+            assert_eq!(pasta::Ep::from(p).to_bytes(), p.to_bytes());
+            assert_eq!(Ep::from(pasta::Ep::from(p)), p);
+            let p = p.to_affine();
+            assert_eq!(EpAffine::from(pasta::EpAffine::from(p)), p);
+
+            let q = Eq::random(&mut rng);
+            assert_eq!(pasta::Eq::from(q).to_bytes(), q.to_bytes());
+            assert_eq!(Eq::from(pasta::Eq::from(q)), q);
+        }
+
+        assert!(bool::from(
+            pasta::EpAffine::from(EpAffine::identity()).is_identity()
+        ));
+        assert!(bool::from(
+            EqAffine::from(pasta::EqAffine::identity()).is_identity()
+        ));
+    }
+}
@@ -1,84 +1,380 @@
 //! This module implements "simplified SWU" hashing to short Weierstrass curves
-//! with a = 0.
+//! with a = 0, and the message expansion functions of RFC 9380 that the hash-to-curve
+//! suites are built on.
+//!
+//! [`Blake2b512`] is the hash function of
+//! [`CurveExt::hash_to_curve`](crate::arithmetic::CurveExt::hash_to_curve), which
+//! [`Ep::hash_to_curve_with_hash`](crate::Ep::hash_to_curve_with_hash) and
+//! [`Eq::hash_to_curve_with_hash`](crate::Eq::hash_to_curve_with_hash) generalize to
+//! any [`PrefixedXmdHash`].
+//!
+//! [`ExpandMessage`] selects the expansion used by
+//! [`Ep::hash_to_curve_with`](crate::Ep::hash_to_curve_with) and
+//! [`Eq::hash_to_curve_with`](crate::Eq::hash_to_curve_with): either
+//! [`ExpandMsgXmd`] with a hash function such as `Sha256` (with the
+//! `hash-to-curve-sha2` feature flag), or [`ExpandMsgXof`] with an extendable-output
+//! function such as `Shake128` (with the `hash-to-curve-sha3` feature flag).
+
+use alloc::vec;
+use core::marker::PhantomData;
 
 use ff::{Field, FromUniformBytes, PrimeField};
-use static_assertions::const_assert;
 use subtle::ConstantTimeEq;
 
 use crate::arithmetic::CurveExt;
 
-/// Hashes over a message and writes the output to all of `buf`.
-pub fn hash_to_field<F: FromUniformBytes<64>>(
+/// Hashes `message` to field elements, writing the output to all of `buf`, in the
+/// same way as the hash-to-curve functions that take a domain prefix.
+///
+/// This uses `expand_message_xmd` with `H` and the domain separation tag
+/// `{domain_prefix}-{curve_id}_XMD:{H::NAME}_SSWU_RO_`, and derives each field
+/// element from 64 bytes of its output. See [`PrefixedXmdHash`] for why `H` cannot
+/// be `Sha256`; use [`hash_to_field_with`] for the RFC 9380 suites instead. With two elements, [`Blake2b512`] and the
+/// [`CURVE_ID`](crate::arithmetic::CurveExt::CURVE_ID) of a curve, these are the
+/// field elements that [`CurveExt::hash_to_curve`] maps to the curve.
+///
+/// # Examples
+///
+/// ```
+/// use pasta_curves::arithmetic::CurveExt;
+/// use pasta_curves::hashtocurve::{hash_to_field, Blake2b512};
+/// use pasta_curves::{pallas, Fp};
+///
+/// let mut challenges = [Fp::zero(); 3];
+/// hash_to_field::<Blake2b512, _>(
+///     pallas::Point::CURVE_ID,
+///     "z.cash:example_challenges",
+///     b"transcript",
+///     &mut challenges,
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Panics if the domain separation tag is longer than 255 bytes, or `buf` is too long
+/// for [`expand_message_xmd`] with `H` to produce `64 * buf.len()` bytes.
+pub fn hash_to_field<H: PrefixedXmdHash, F: FromUniformBytes<64>>(
     curve_id: &str,
     domain_prefix: &str,
     message: &[u8],
-    buf: &mut [F; 2],
+    buf: &mut [F],
 ) {
-    assert!(domain_prefix.len() < 256);
-    assert!((22 + curve_id.len() + domain_prefix.len()) < 256);
+    let parts = [domain_prefix, "-", curve_id, "_XMD:", H::NAME, "_SSWU_RO_"];
+    let mut dst = [0u8; 255];
+    let mut dst_len = 0;
+    for part in parts {
+        assert!(dst_len + part.len() < 256);
+        dst[dst_len..dst_len + part.len()].copy_from_slice(part.as_bytes());
+        dst_len += part.len();
+    }
 
     // Assume that the field size is 32 bytes and k is 256, where k is defined in
     // <https://www.ietf.org/archive/id/draft-irtf-cfrg-hash-to-curve-10.html#name-security-considerations-3>.
     const CHUNKLEN: usize = 64;
-    const_assert!(CHUNKLEN * 2 < 256);
-
-    // Input block size of BLAKE2b.
-    const R_IN_BYTES: usize = 128;
-
-    let personal = [0u8; 16];
-    let empty_hasher = blake2b_simd::Params::new()
-        .hash_length(CHUNKLEN)
-        .personal(&personal)
-        .to_state();
-
-    let b_0 = empty_hasher
-        .clone()
-        .update(&[0; R_IN_BYTES])
-        .update(message)
-        .update(&[0, (CHUNKLEN * 2) as u8, 0])
-        .update(domain_prefix.as_bytes())
-        .update(b"-")
-        .update(curve_id.as_bytes())
-        .update(b"_XMD:BLAKE2b_SSWU_RO_")
-        .update(&[(22 + curve_id.len() + domain_prefix.len()) as u8])
-        .finalize();
-
-    let b_1 = empty_hasher
-        .clone()
-        .update(b_0.as_array())
-        .update(&[1])
-        .update(domain_prefix.as_bytes())
-        .update(b"-")
-        .update(curve_id.as_bytes())
-        .update(b"_XMD:BLAKE2b_SSWU_RO_")
-        .update(&[(22 + curve_id.len() + domain_prefix.len()) as u8])
-        .finalize();
-
-    let b_2 = {
-        let mut empty_hasher = empty_hasher;
-        for (l, r) in b_0.as_array().iter().zip(b_1.as_array().iter()) {
-            empty_hasher.update(&[*l ^ *r]);
-        }
-        empty_hasher
-            .update(&[2])
-            .update(domain_prefix.as_bytes())
-            .update(b"-")
-            .update(curve_id.as_bytes())
-            .update(b"_XMD:BLAKE2b_SSWU_RO_")
-            .update(&[(22 + curve_id.len() + domain_prefix.len()) as u8])
-            .finalize()
-    };
 
-    for (big, buf) in [b_1, b_2].iter().zip(buf.iter_mut()) {
+    let mut uniform_bytes = vec![0u8; buf.len() * CHUNKLEN];
+    expand_message_xmd::<H>(message, &dst[..dst_len], &mut uniform_bytes);
+
+    for (big, buf) in uniform_bytes.chunks(CHUNKLEN).zip(buf.iter_mut()) {
         let mut little = [0u8; CHUNKLEN];
-        little.copy_from_slice(big.as_array());
+        little.copy_from_slice(big);
         little.reverse();
         *buf = F::from_uniform_bytes(&little);
     }
 }
 
+/// A hash function that can be used by [`expand_message_xmd`].
+pub trait XmdHash: Clone + Default {
+    /// The name of the hash function in hash-to-curve suite IDs, such as `SHA-256`.
+    const NAME: &'static str;
+
+    /// The input block size of the hash function in bytes (`s_in_bytes` in RFC 9380).
+    const BLOCK_SIZE: usize;
+
+    /// The output size of the hash function in bytes (`b_in_bytes` in RFC 9380),
+    /// which must be at most 64.
+    const OUTPUT_SIZE: usize;
+
+    /// Absorbs `data` into the hash state.
+    fn update(&mut self, data: &[u8]);
+
+    /// Writes the digest to the first [`Self::OUTPUT_SIZE`] bytes of `out`.
+    fn finalize(self, out: &mut [u8; 64]);
+}
+
+/// A hash function for [`hash_to_field`] and the hash-to-curve functions that take a
+/// domain prefix.
+///
+/// These derive each field element from 64 bytes of `expand_message_xmd` output,
+/// under a domain separation tag ending with `{curve_id}_XMD:{H::NAME}_SSWU_RO_`.
+/// That has the form of an RFC 9380 suite ID, but the suites derive each element
+/// from 48 bytes, so a hash function for which such a suite is implemented must not
+/// implement this trait: otherwise the same suite ID would give two different
+/// points. In particular, `Sha256` does not implement it, because
+/// `pallas_XMD:SHA-256_SSWU_RO_` and `vesta_XMD:SHA-256_SSWU_RO_` are the suites of
+/// `Ep::hash_to_curve_sha256` and `Eq::hash_to_curve_sha256`.
+pub trait PrefixedXmdHash: XmdHash {}
+
+/// The BLAKE2b hash function with a 64-byte output, which is used by
+/// [`CurveExt::hash_to_curve`].
+#[derive(Clone, Debug)]
+pub struct Blake2b512(blake2b_simd::State);
+
+impl Default for Blake2b512 {
+    fn default() -> Self {
+        Blake2b512(
+            blake2b_simd::Params::new()
+                .hash_length(64)
+                .personal(&[0; 16])
+                .to_state(),
+        )
+    }
+}
+
+impl XmdHash for Blake2b512 {
+    const NAME: &'static str = "BLAKE2b";
+    const BLOCK_SIZE: usize = 128;
+    const OUTPUT_SIZE: usize = 64;
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self, out: &mut [u8; 64]) {
+        out.copy_from_slice(self.0.finalize().as_array());
+    }
+}
+
+impl PrefixedXmdHash for Blake2b512 {}
+
+/// The SHA-256 hash function, which is used by the RFC 9380 suites of
+/// [`Ep::hash_to_curve_sha256`](crate::Ep::hash_to_curve_sha256) and
+/// [`Eq::hash_to_curve_sha256`](crate::Eq::hash_to_curve_sha256).
+#[cfg(feature = "hash-to-curve-sha2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hash-to-curve-sha2")))]
+#[derive(Clone, Debug, Default)]
+pub struct Sha256(sha2::Sha256);
+
+#[cfg(feature = "hash-to-curve-sha2")]
+impl XmdHash for Sha256 {
+    const NAME: &'static str = "SHA-256";
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 32;
+
+    fn update(&mut self, data: &[u8]) {
+        sha2::Digest::update(&mut self.0, data);
+    }
+
+    fn finalize(self, out: &mut [u8; 64]) {
+        out[..32].copy_from_slice(&sha2::Digest::finalize(self.0));
+    }
+}
+
+/// Fills `out` with bytes derived from `message` and the domain separation tag
+/// `dst`, using `expand_message_xmd` from [RFC 9380, section 5.3.1].
+///
+/// # Panics
+///
+/// Panics if `dst` is longer than 255 bytes, or `out` is longer than 65535 bytes
+/// or 255 outputs of `H`.
+///
+/// [RFC 9380, section 5.3.1]: https://www.rfc-editor.org/rfc/rfc9380.html#section-5.3.1
+pub fn expand_message_xmd<H: XmdHash>(message: &[u8], dst: &[u8], out: &mut [u8]) {
+    assert!(H::OUTPUT_SIZE <= 64);
+    assert!(dst.len() < 256);
+    let ell = (out.len() + H::OUTPUT_SIZE - 1) / H::OUTPUT_SIZE;
+    assert!(ell < 256 && out.len() < 65536);
+    let dst_len = [dst.len() as u8];
+
+    // b_0 = H(Z_pad || msg || l_i_b_str || I2OSP(0, 1) || DST_prime)
+    let mut b_0 = [0u8; 64];
+    let mut hasher = H::default();
+    let mut z_pad = H::BLOCK_SIZE;
+    while z_pad > 0 {
+        let n = z_pad.min(64);
+        hasher.update(&[0; 64][..n]);
+        z_pad -= n;
+    }
+    hasher.update(message);
+    hasher.update(&(out.len() as u16).to_be_bytes());
+    hasher.update(&[0]);
+    hasher.update(dst);
+    hasher.update(&dst_len);
+    hasher.finalize(&mut b_0);
+
+    // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime), where b_1 is
+    // computed the same way from an all-zeroes b_0.
+    let mut b_i = [0u8; 64];
+    for (i, chunk) in out.chunks_mut(H::OUTPUT_SIZE).enumerate() {
+        let mut xored = [0u8; 64];
+        for ((x, l), r) in xored.iter_mut().zip(b_0.iter()).zip(b_i.iter()) {
+            *x = l ^ r;
+        }
+
+        let mut hasher = H::default();
+        hasher.update(&xored[..H::OUTPUT_SIZE]);
+        hasher.update(&[(i + 1) as u8]);
+        hasher.update(dst);
+        hasher.update(&dst_len);
+        hasher.finalize(&mut b_i);
+        chunk.copy_from_slice(&b_i[..chunk.len()]);
+    }
+}
+
+/// An extendable-output function that can be used by [`expand_message_xof`].
+pub trait XofHash: Clone + Default {
+    /// The name of the function in hash-to-curve suite IDs, such as `SHAKE128`.
+    const NAME: &'static str;
+
+    /// Absorbs `data` into the state.
+    fn update(&mut self, data: &[u8]);
+
+    /// Fills `out` with output.
+    fn finalize_into(self, out: &mut [u8]);
+}
+
+#[cfg(feature = "hash-to-curve-sha3")]
+macro_rules! impl_xof_hash {
+    ($name:ident, $id:literal) => {
+        impl XofHash for $name {
+            const NAME: &'static str = $id;
+
+            fn update(&mut self, data: &[u8]) {
+                sha3::digest::Update::update(&mut self.0, data);
+            }
+
+            fn finalize_into(self, out: &mut [u8]) {
+                sha3::digest::ExtendableOutput::finalize_xof_into(self.0, out);
+            }
+        }
+    };
+}
+
+/// The SHAKE128 extendable-output function.
+#[cfg(feature = "hash-to-curve-sha3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hash-to-curve-sha3")))]
+#[derive(Clone, Debug, Default)]
+pub struct Shake128(sha3::Shake128);
+
+/// The SHAKE256 extendable-output function.
+#[cfg(feature = "hash-to-curve-sha3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hash-to-curve-sha3")))]
+#[derive(Clone, Debug, Default)]
+pub struct Shake256(sha3::Shake256);
+
+#[cfg(feature = "hash-to-curve-sha3")]
+impl_xof_hash!(Shake128, "SHAKE128");
+#[cfg(feature = "hash-to-curve-sha3")]
+impl_xof_hash!(Shake256, "SHAKE256");
+
+/// Fills `out` with bytes derived from `message` and the domain separation tag
+/// `dst`, using `expand_message_xof` from [RFC 9380, section 5.3.2].
+///
+/// # Panics
+///
+/// Panics if `dst` is longer than 255 bytes, or `out` is longer than 65535 bytes.
+///
+/// [RFC 9380, section 5.3.2]: https://www.rfc-editor.org/rfc/rfc9380.html#section-5.3.2
+pub fn expand_message_xof<X: XofHash>(message: &[u8], dst: &[u8], out: &mut [u8]) {
+    assert!(dst.len() < 256);
+    assert!(out.len() < 65536);
+
+    // H(msg || I2OSP(len_in_bytes, 2) || DST_prime, len_in_bytes)
+    let mut xof = X::default();
+    xof.update(message);
+    xof.update(&(out.len() as u16).to_be_bytes());
+    xof.update(dst);
+    xof.update(&[dst.len() as u8]);
+    xof.finalize_into(out);
+}
+
+/// A message expansion function from [RFC 9380, section 5.3], which hash-to-curve
+/// suites use to derive field elements from a message.
+///
+/// [RFC 9380, section 5.3]: https://www.rfc-editor.org/rfc/rfc9380.html#section-5.3
+pub trait ExpandMessage {
+    /// Fills `out` with bytes derived from `message` and the domain separation tag
+    /// `dst`.
+    ///
+    /// Implementations may panic if `dst` is longer than 255 bytes or `out` is
+    /// longer than 65535 bytes.
+    fn expand_message(message: &[u8], dst: &[u8], out: &mut [u8]);
+}
+
+/// [`expand_message_xmd`] with the hash function `H`.
+#[derive(Debug)]
+pub struct ExpandMsgXmd<H>(PhantomData<H>);
+
+impl<H: XmdHash> ExpandMessage for ExpandMsgXmd<H> {
+    fn expand_message(message: &[u8], dst: &[u8], out: &mut [u8]) {
+        expand_message_xmd::<H>(message, dst, out)
+    }
+}
+
+/// [`expand_message_xof`] with the extendable-output function `X`.
+#[derive(Debug)]
+pub struct ExpandMsgXof<X>(PhantomData<X>);
+
+impl<X: XofHash> ExpandMessage for ExpandMsgXof<X> {
+    fn expand_message(message: &[u8], dst: &[u8], out: &mut [u8]) {
+        expand_message_xof::<X>(message, dst, out)
+    }
+}
+
+/// The number of bytes from which each field element is derived by the RFC 9380
+/// suites, `L = ceil((ceil(log2(p)) + k) / 8)` for the security parameter `k = 128`.
+const L: usize = 48;
+
+/// Hashes `message` to field elements with the domain separation tag `dst`, writing
+/// the output to all of `buf`, as specified by `hash_to_field` in
+/// [RFC 9380, section 5.2] for the message expansion `E`.
+///
+/// Each field element is derived from `L = 48` bytes of the expanded message, as
+/// required for the 128-bit security level of the curves. With two elements, these
+/// are the field elements that [`Ep::hash_to_curve_with`](crate::Ep::hash_to_curve_with)
+/// and [`Eq::hash_to_curve_with`](crate::Eq::hash_to_curve_with) map to the curve.
+///
+/// # Panics
+///
+/// Panics if `dst` is longer than 255 bytes, or `buf` is too long for `E` to expand
+/// the message to `48 * buf.len()` bytes.
+///
+/// [RFC 9380, section 5.2]: https://www.rfc-editor.org/rfc/rfc9380.html#section-5.2
+pub fn hash_to_field_with<E: ExpandMessage, F: FromUniformBytes<64>>(
+    dst: &[u8],
+    message: &[u8],
+    buf: &mut [F],
+) {
+    let mut uniform_bytes = vec![0u8; buf.len() * L];
+    E::expand_message(message, dst, &mut uniform_bytes);
+
+    for (big, buf) in uniform_bytes.chunks(L).zip(buf.iter_mut()) {
+        // OS2IP(big) mod p
+        let mut little = [0u8; 64];
+        little[..L].copy_from_slice(big);
+        little[..L].reverse();
+        *buf = F::from_uniform_bytes(&little);
+    }
+}
+
+/// Maps the output of a `hash_to_field` to a point of `C`, by mapping each field
+/// element to the isogenous curve `I` with the simplified SWU map, adding the
+/// results, and applying the isogeny `iso`.
+pub(crate) fn map_to_curve<F: PrimeField, C: CurveExt<Base = F>, I: CurveExt<Base = F>>(
+    us: &[F; 2],
+    theta: F,
+    z: F,
+    iso: &[F; 13],
+) -> C {
+    let q0 = map_to_curve_simple_swu::<F, C, I>(&us[0], theta, z);
+    let q1 = map_to_curve_simple_swu::<F, C, I>(&us[1], theta, z);
+    let r = q0 + &q1;
+    debug_assert!(bool::from(r.is_on_curve()));
+    iso_map::<F, C, I>(&r, iso)
+}
+
 /// Implements a degree 3 isogeny map.
-pub fn iso_map<F: Field, C: CurveExt<Base = F>, I: CurveExt<Base = F>>(
+pub(crate) fn iso_map<F: Field, C: CurveExt<Base = F>, I: CurveExt<Base = F>>(
     p: &I,
     iso: &[C::Base; 13],
 ) -> C {
@@ -106,7 +402,11 @@ pub fn iso_map<F: Field, C: CurveExt<Base = F>, I: CurveExt<Base = F>>(
 }
 
 #[allow(clippy::many_single_char_names)]
-pub fn map_to_curve_simple_swu<F: PrimeField, C: CurveExt<Base = F>, I: CurveExt<Base = F>>(
+pub(crate) fn map_to_curve_simple_swu<
+    F: PrimeField,
+    C: CurveExt<Base = F>,
+    I: CurveExt<Base = F>,
+>(
     u: &F,
     theta: F,
     z: F,
@@ -176,3 +476,158 @@ pub fn map_to_curve_simple_swu<F: PrimeField, C: CurveExt<Base = F>, I: CurveExt
 
     I::new_jacobian(num_x * div, y * div3, div).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    pub(super) fn hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[cfg(feature = "hash-to-curve-sha2")]
+    #[test]
+    fn test_expand_message_xmd_sha256() {
+        // RFC 9380, appendix K.1
+        const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let a512 = [b"a512_".as_slice(), &[b'a'; 512]].concat();
+        let vectors: [(&[u8], &str); 5] = [
+            (
+                b"",
+                "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+            ),
+            (
+                b"abc",
+                "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+            ),
+            (
+                &a512,
+                "4623227bcc01293b8c130bf771da8c298dede7383243dc0993d2d94823958c4c",
+            ),
+            (
+                b"",
+                "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbe\
+                 e0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18\
+                 eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dc\
+                 c541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced",
+            ),
+            (
+                b"abcdef0123456789",
+                "ef904a29bffc4cf9ee82832451c946ac3c8f8058ae97d8d629831a74c6572bd9\
+                 ebd0df635cd1f208e2038e760c4994984ce73f0d55ea9f22af83ba4734569d4b\
+                 c95e18350f740c07eef653cbb9f87910d833751825f0ebefa1abe5420bb52be1\
+                 4cf489b37fe1a72f7de2d10be453b2c9d9eb20c7e3f6edc5a60629178d9478df",
+            ),
+        ];
+
+        for (message, expected) in vectors {
+            let expected = hex(expected);
+            let mut out = std::vec![0; expected.len()];
+            expand_message_xmd::<Sha256>(message, DST, &mut out);
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[cfg(feature = "hash-to-curve-sha3")]
+    #[test]
+    fn test_expand_message_xof() {
+        // RFC 9380, appendix K.6
+        const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHAKE128";
+        let vectors: [(&[u8], &str); 4] = [
+            (
+                b"",
+                "86518c9cd86581486e9485aa74ab35ba150d1c75c88e26b7043e44e2acd735a2",
+            ),
+            (
+                b"abc",
+                "8696af52a4d862417c0763556073f47bc9b9ba43c99b505305cb1ec04a9ab468",
+            ),
+            (
+                b"",
+                "7314ff1a155a2fb99a0171dc71b89ab6e3b2b7d59e38e64419b8b6294d03ffee\
+                 42491f11370261f436220ef787f8f76f5b26bdcd850071920ce023f3ac468477\
+                 44f4612b8714db8f5db83205b2e625d95afd7d7b4d3094d3bdde815f52850bb4\
+                 1ead9822e08f22cf41d615a303b0d9dde73263c049a7b9898208003a739a2e57",
+            ),
+            (
+                b"abcdef0123456789",
+                "19b65ee7afec6ac06a144f2d6134f08eeec185f1a890fe34e68f0e377b7d0312\
+                 883c048d9b8a1d6ecc3b541cb4987c26f45e0c82691ea299b5e6889bbfe58915\
+                 3016d8131717ba26f07c3c14ffbef1f3eff9752e5b6183f43871a78219a75e70\
+                 00fbac6a7072e2b83c790a3a5aecd9d14be79f9fd4fb180960a3772e08680495",
+            ),
+        ];
+
+        for (message, expected) in vectors {
+            let expected = hex(expected);
+            let mut out = std::vec![0; expected.len()];
+            ExpandMsgXof::<Shake128>::expand_message(message, DST, &mut out);
+            assert_eq!(out, expected);
+        }
+
+        // RFC 9380, appendix K.7
+        const DST_256: &[u8] = b"QUUX-V01-CS02-with-expander-SHAKE256";
+        let mut out = [0; 32];
+        expand_message_xof::<Shake256>(b"abc", DST_256, &mut out);
+        assert_eq!(
+            out.as_slice(),
+            hex("b39e493867e2767216792abce1f2676c197c0692aed061560ead251821808e07")
+        );
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        use crate::{Ep, Fp};
+
+        let message = b"Trans rights now!";
+
+        // Two elements are the inputs to the map of `CurveExt::hash_to_curve`.
+        let mut us = [Fp::zero(); 2];
+        hash_to_field::<Blake2b512, _>(Ep::CURVE_ID, "z.cash:test", message, &mut us);
+        assert_eq!(
+            map_to_curve::<Fp, Ep, crate::curves::IsoEp>(
+                &us,
+                Ep::THETA,
+                Ep::Z,
+                &Ep::ISOGENY_CONSTANTS
+            ),
+            Ep::hash_to_curve("z.cash:test")(message)
+        );
+
+        // Computed with scripts/hash_to_curve_vectors.py.
+        let mut us = [Fp::zero(); 3];
+        hash_to_field::<Blake2b512, _>(Ep::CURVE_ID, "z.cash:test", message, &mut us);
+        assert_eq!(
+            us.map(|u| format!("{:?}", u)),
+            [
+                "0x054210e6deabde931351480d2352c39d04dce737e2916d7a4abcf1738fa9b02c",
+                "0x21a99538b9b98393f83ac630382db71af9a6a75c19768de86c758025abe01683",
+                "0x203c738a602536f3b80aecf416087191ab3d200fc13664ead9642595ca46c1de",
+            ]
+        );
+
+        #[cfg(feature = "hash-to-curve-sha2")]
+        {
+            use crate::Fq;
+
+            let mut us = [Fq::zero(); 3];
+            hash_to_field_with::<ExpandMsgXmd<Sha256>, _>(
+                b"QUUX-V01-CS02-with-vesta_XMD:SHA-256_SSWU_RO_",
+                b"abc",
+                &mut us,
+            );
+            assert_eq!(
+                us.map(|u| format!("{:?}", u)),
+                [
+                    "0x2423eac52ef4bcabbf7410ae7395c813cbd5245495a90fc94f093eb57a2c263c",
+                    "0x06572ab35c8c636b7a2093ee697bba128e860bf7f7cd7c7f1af27d9494a6039f",
+                    "0x2e8d1888b0662c859f2ad277faace6be849f4ab4d34da26f4931576b4c788521",
+                ]
+            );
+        }
+    }
+}
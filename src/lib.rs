@@ -15,25 +15,58 @@ extern crate alloc;
 #[cfg(test)]
 #[macro_use]
 extern crate std;
+#[cfg(all(not(test), feature = "std"))]
+extern crate std;
 
 #[macro_use]
 mod macros;
 mod curves;
+mod error;
 mod fields;
+mod glv;
 
 pub mod arithmetic;
+#[cfg(feature = "custom-backend")]
+#[cfg_attr(docsrs, doc(cfg(feature = "custom-backend")))]
+pub mod backend;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod hashtocurve;
 pub mod pallas;
 pub mod vesta;
 
-#[cfg(feature = "alloc")]
-mod hashtocurve;
+#[cfg(feature = "arkworks")]
+mod arkworks_impl;
+
+#[cfg(feature = "borsh")]
+mod borsh_impl;
+
+#[cfg(feature = "crypto-bigint")]
+mod crypto_bigint_impl;
+
+#[cfg(feature = "halo2curves")]
+mod halo2curves_impl;
+
+#[cfg(feature = "num-bigint")]
+mod num_bigint_impl;
+
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+
+#[cfg(feature = "scale")]
+mod scale_impl;
 
 #[cfg(feature = "serde")]
 mod serde_impl;
 
 pub use curves::*;
+pub use error::DecodeError;
 pub use fields::*;
 
+#[cfg(feature = "rkyv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+pub use rkyv_impl::{ArchivedEpAffine, ArchivedEqAffine, ArchivedFp, ArchivedFq};
+
 pub extern crate group;
 
 #[cfg(feature = "alloc")]
@@ -43,6 +76,7 @@ fn test_endo_consistency() {
     use group::{ff::WithSmallOrderMulGroup, Group};
 
     let a = pallas::Point::generator();
+    assert_eq!(CurveExt::endo(&a), a.endo());
     assert_eq!(a * pallas::Scalar::ZETA, a.endo());
     let a = vesta::Point::generator();
     assert_eq!(a * vesta::Scalar::ZETA, a.endo());
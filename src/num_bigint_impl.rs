@@ -0,0 +1,116 @@
+use ff::PrimeField;
+use num_bigint::BigUint;
+
+use crate::fields::{Fp, Fq, ParseFieldError};
+
+macro_rules! impl_biguint_conversions {
+    ($field:ident) => {
+        impl $field {
+            /// Returns the field modulus as a [`BigUint`].
+            pub fn modulus_biguint() -> BigUint {
+                BigUint::from_bytes_le((-$field::one()).to_repr().as_ref()) + 1u32
+            }
+
+            /// Converts a [`BigUint`] into a field element, reducing it modulo the field
+            /// modulus.
+            ///
+            /// Use [`TryFrom`] instead to reject values that are not canonical.
+            pub fn from_biguint_reduced(n: &BigUint) -> Self {
+                Self::try_from(n % Self::modulus_biguint()).unwrap()
+            }
+        }
+
+        impl From<&$field> for BigUint {
+            fn from(a: &$field) -> BigUint {
+                BigUint::from_bytes_le(a.to_repr().as_ref())
+            }
+        }
+
+        impl From<$field> for BigUint {
+            fn from(a: $field) -> BigUint {
+                BigUint::from(&a)
+            }
+        }
+
+        impl TryFrom<&BigUint> for $field {
+            type Error = ParseFieldError;
+
+            /// Converts a [`BigUint`] into a field element, returning an error if it is
+            /// not less than the field modulus.
+            fn try_from(n: &BigUint) -> Result<Self, Self::Error> {
+                let bytes = n.to_bytes_le();
+                if bytes.len() > 32 {
+                    return Err(ParseFieldError::out_of_range());
+                }
+
+                let mut repr = <$field as PrimeField>::Repr::default();
+                repr.as_mut()[..bytes.len()].copy_from_slice(&bytes);
+                Option::from($field::from_repr(repr)).ok_or(ParseFieldError::out_of_range())
+            }
+        }
+
+        impl TryFrom<BigUint> for $field {
+            type Error = ParseFieldError;
+
+            fn try_from(n: BigUint) -> Result<Self, Self::Error> {
+                $field::try_from(&n)
+            }
+        }
+    };
+}
+
+impl_biguint_conversions!(Fp);
+impl_biguint_conversions!(Fq);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::Field;
+
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    macro_rules! test_biguint {
+        ($name:ident, $field:ident) => {
+            #[test]
+            fn $name() {
+                let mut rng = XorShiftRng::from_seed([
+                    0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54,
+                    0x06, 0xbc, 0xe5,
+                ]);
+
+                assert_eq!(BigUint::from($field::zero()), BigUint::from(0u64));
+                assert_eq!(BigUint::from($field::one()), BigUint::from(1u64));
+
+                for _ in 0..100 {
+                    let a = $field::random(&mut rng);
+                    let b = $field::random(&mut rng);
+                    assert_eq!($field::try_from(BigUint::from(a)).unwrap(), a);
+                    assert_eq!(
+                        $field::from_biguint_reduced(&(BigUint::from(a) * BigUint::from(b))),
+                        a * b
+                    );
+                }
+
+                let m = $field::modulus_biguint();
+                assert_eq!(
+                    $field::try_from(&m - BigUint::from(1u64)).unwrap(),
+                    -$field::one()
+                );
+                assert_eq!($field::try_from(&m), Err(ParseFieldError::out_of_range()));
+                assert_eq!(
+                    $field::try_from(BigUint::from(1u64) + m.pow(2)),
+                    Err(ParseFieldError::out_of_range())
+                );
+                assert_eq!($field::from_biguint_reduced(&m), $field::zero());
+                assert_eq!(
+                    $field::from_biguint_reduced(&(&m * &m + BigUint::from(5u64))),
+                    $field::from(5)
+                );
+            }
+        };
+    }
+
+    test_biguint!(test_fp_biguint, Fp);
+    test_biguint!(test_fq_biguint, Fq);
+}
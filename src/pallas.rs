@@ -14,6 +14,21 @@ pub type Point = Ep;
 /// A Pallas point in the affine coordinate space (or the point at infinity).
 pub type Affine = EpAffine;
 
+/// Decomposes `k` for the Pallas endomorphism `(x, y) -> (Base::ZETA * x, y)`, which
+/// maps each point `P` to `[Scalar::ZETA] P`.
+///
+/// Returns `(k1, k2, (k1_neg, k2_neg))` such that `k = ±k1 ± k2 * Scalar::ZETA`,
+/// where each sign is negative if the corresponding flag is set. This is the
+/// decomposition used by [`Ep::mul_glv`], and it is computed in constant time.
+pub fn decompose_scalar(k: &Scalar) -> (u128, u128, (bool, bool)) {
+    let (k1, k1_neg, k2, k2_neg) = k.glv_decompose();
+    (
+        crate::glv::to_u128(&k1),
+        crate::glv::to_u128(&k2),
+        (k1_neg.into(), k2_neg.into()),
+    )
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 #[allow(clippy::many_single_char_names)]
@@ -22,8 +37,9 @@ fn test_iso_map() {
     use group::Group;
 
     // This is a regression test (it's the same input to iso_map as for hash_to_curve
-    // with domain prefix "z.cash:test", Shake128, and input b"hello"). We don't
-    // implement Shake128 any more but that's fine.
+    // with domain prefix "z.cash:test", Shake128, and input b"hello" in an early
+    // version of this crate). That construction predates RFC 9380 and is not the same
+    // as hash_to_curve_with::<ExpandMsgXof<Shake128>>, but that's fine.
     let r = super::IsoEp::new_jacobian(
         Base::from_raw([
             0xc37f111df5c4419e,
@@ -162,3 +178,824 @@ fn test_hash_to_curve() {
     assert!(bool::from(p.is_on_curve()));
     assert!(bool::from(p.is_identity()));
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_hash_to_curve_with_hash() {
+    use crate::arithmetic::CurveExt;
+    use crate::hashtocurve::Blake2b512;
+
+    let message = b"Trans rights now!";
+    assert_eq!(
+        Ep::hash_to_curve_with_hash::<Blake2b512>("z.cash:test")(message),
+        Ep::hash_to_curve("z.cash:test")(message)
+    );
+}
+
+#[cfg(feature = "hash-to-curve-sha2")]
+#[test]
+fn test_hash_to_curve_sha256() {
+    use group::Curve;
+
+    // Computed with scripts/hash_to_curve_vectors.py.
+    assert_eq!(Ep::SHA256_SUITE_ID, "pallas_XMD:SHA-256_SSWU_RO_");
+    let hash = Ep::hash_to_curve_sha256(b"QUUX-V01-CS02-with-pallas_XMD:SHA-256_SSWU_RO_");
+    let vectors: [(&[u8], &str, &str); 3] = [
+        (
+            b"",
+            "0x08d9956f24425586fa155c4aabdfdd6627e5078c4f9d40b12a735a1f47521a8b",
+            "0x328efeab447f35caab39209b4d8e17b31443ce0c24f3a1ec6b8db51399e7f146",
+        ),
+        (
+            b"abc",
+            "0x354714330e244e1ba390e700f62f122ea90c7b10efed2e2c960501f3939c2a71",
+            "0x010908f7cfc94e040bf741e4759d71cf598d3298cb51173ce77ee363d6109417",
+        ),
+        (
+            b"abcdef0123456789",
+            "0x19f3d994235c0a2ccd3578bf184cf2bbd75323bd9ff39c9315cd1630f83c1fc9",
+            "0x3094fd161b8631bd8022b6a2fb2a3cd7aa4defa5632ddba948d62b1b41bd4814",
+        ),
+    ];
+    for (message, x, y) in vectors {
+        let p = hash(message).to_affine();
+        assert_eq!(format!("{:?}", p.x()), x);
+        assert_eq!(format!("{:?}", p.y()), y);
+    }
+}
+
+#[cfg(feature = "hash-to-curve-sha3")]
+#[test]
+fn test_hash_to_curve_shake128() {
+    use crate::hashtocurve::{ExpandMsgXof, Shake128};
+    use group::Curve;
+
+    // Computed with scripts/hash_to_curve_vectors.py.
+    let hash = Ep::hash_to_curve_with::<ExpandMsgXof<Shake128>>(
+        b"QUUX-V01-CS02-with-pallas_XOF:SHAKE128_SSWU_RO_",
+    );
+    let vectors: [(&[u8], &str, &str); 2] = [
+        (
+            b"",
+            "0x06d046134c432423ec2c8b3e4711c563ba4196996bf59081b159c5de0bb777f4",
+            "0x0ed08b0783a7d9e0e13674c6a53403becc94244e60341ff7ff36fc05262b6fca",
+        ),
+        (
+            b"abc",
+            "0x1db754acf7a2adf75771c6a14072f0709581fb7bebe3ce107a08af728f4b3c4d",
+            "0x35c5a5e71206ae569efd648f45c490c257d75c010f731a4c014007f093bfd6ba",
+        ),
+    ];
+    for (message, x, y) in vectors {
+        let p = hash(message).to_affine();
+        assert_eq!(format!("{:?}", p.x()), x);
+        assert_eq!(format!("{:?}", p.y()), y);
+    }
+}
+
+#[test]
+fn test_hex() {
+    use group::{prime::PrimeCurveAffine, GroupEncoding};
+    use std::string::String;
+
+    let g = EpAffine::generator();
+    let bytes = g.to_bytes();
+    let lower: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    let upper: String = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+    assert_eq!(format!("{:x}", g), lower);
+    assert_eq!(format!("{:X}", g), upper);
+    assert_eq!(format!("{:#x}", g), format!("0x{}", lower));
+    assert_eq!(format!("{:x}", EpAffine::identity()), "0".repeat(64));
+}
+
+#[test]
+fn test_sum() {
+    use group::{Curve, Group};
+
+    let g = Ep::generator();
+    let points = [g, g.double(), -g, g * Fq::from(5)];
+    let affine: std::vec::Vec<EpAffine> = points.iter().map(|p| p.to_affine()).collect();
+    let expected = g * Fq::from(7);
+
+    assert_eq!(points.iter().sum::<Ep>(), expected);
+    assert_eq!(points.iter().copied().sum::<Ep>(), expected);
+    assert_eq!(affine.iter().sum::<Ep>(), expected);
+    assert_eq!(affine.into_iter().sum::<Ep>(), expected);
+    assert_eq!(core::iter::empty::<EpAffine>().sum::<Ep>(), Ep::identity());
+}
+
+#[test]
+fn test_try_from_bytes() {
+    use crate::DecodeError;
+    use group::{prime::PrimeCurveAffine, GroupEncoding};
+
+    let g = EpAffine::generator();
+    assert_eq!(EpAffine::try_from(&g.to_bytes()[..]), Ok(g));
+    assert_eq!(
+        EpAffine::try_from(&EpAffine::identity().to_bytes()[..]),
+        Ok(EpAffine::identity())
+    );
+
+    assert_eq!(
+        EpAffine::try_from(&g.to_bytes()[1..]),
+        Err(DecodeError::InvalidLength {
+            expected: 32,
+            actual: 31
+        })
+    );
+    assert_eq!(
+        EpAffine::try_from(&[0xff; 32][..]),
+        Err(DecodeError::NonCanonical)
+    );
+
+    // The compression flag must not be set for the identity.
+    let mut bytes = [0u8; 32];
+    bytes[31] = 0x80;
+    assert_eq!(
+        EpAffine::try_from_bytes(&bytes),
+        Err(DecodeError::InvalidCompressionFlag)
+    );
+
+    // Find an x-coordinate for which x^3 + 5 is not a square.
+    let mut bytes = [0u8; 32];
+    bytes[0] = 1;
+    while bool::from(EpAffine::from_bytes(&bytes).is_some()) {
+        bytes[0] += 1;
+    }
+    assert_eq!(
+        EpAffine::try_from_bytes(&bytes),
+        Err(DecodeError::NotOnCurve)
+    );
+}
+
+#[test]
+fn test_hash() {
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use std::collections::HashSet;
+
+    let g = EpAffine::generator();
+    let points = [EpAffine::identity(), g, (g + g).to_affine(), -g];
+    let set: HashSet<EpAffine> = points.iter().chain(points.iter()).copied().collect();
+    assert_eq!(set.len(), points.len());
+    assert!(points.iter().all(|p| set.contains(p)));
+    assert!(!set.contains(&(Ep::generator() * Fq::from(3)).to_affine()));
+}
+
+#[test]
+fn test_ord() {
+    use group::{prime::PrimeCurveAffine, Curve, GroupEncoding};
+    use std::collections::BTreeSet;
+
+    let g = EpAffine::generator();
+    let points = [EpAffine::identity(), g, (g + g).to_affine(), -g];
+    let set: BTreeSet<EpAffine> = points.iter().chain(points.iter()).copied().collect();
+    assert_eq!(set.len(), points.len());
+    assert!(set
+        .iter()
+        .zip(set.iter().skip(1))
+        .all(|(a, b)| a.to_bytes() < b.to_bytes()));
+    assert_eq!(set.iter().next(), Some(&EpAffine::identity()));
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_zeroize() {
+    use group::{prime::PrimeCurveAffine, Group};
+    use zeroize::Zeroize;
+
+    let mut a = EpAffine::generator();
+    a.zeroize();
+    assert!(bool::from(a.is_identity()));
+
+    let mut a = Ep::generator();
+    a.zeroize();
+    assert!(bool::from(a.is_identity()));
+}
+
+#[test]
+fn test_distribution() {
+    use group::Group;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    let seed = [
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ];
+    let a: Ep = XorShiftRng::from_seed(seed).gen();
+    let b = Ep::random(XorShiftRng::from_seed(seed));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_mul_glv() {
+    use ff::{Field, PrimeField, WithSmallOrderMulGroup};
+    use group::{Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn double_and_add(p: &Ep, k: &Fq) -> Ep {
+        k.to_repr()
+            .iter()
+            .rev()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+            .fold(Ep::identity(), |acc, bit| {
+                if bit {
+                    acc.double() + p
+                } else {
+                    acc.double()
+                }
+            })
+    }
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for k in [Fq::zero(), Fq::one(), -Fq::one(), Fq::ZETA] {
+        assert_eq!(
+            Ep::generator().mul_glv(&k),
+            double_and_add(&Ep::generator(), &k)
+        );
+    }
+    assert_eq!(
+        Ep::identity().mul_glv(&Fq::random(&mut rng)),
+        Ep::identity()
+    );
+
+    for _ in 0..100 {
+        let p = Ep::random(&mut rng);
+        let k = Fq::random(&mut rng);
+        let expected = double_and_add(&p, &k);
+        assert_eq!(p.mul_glv(&k), expected);
+        assert_eq!(p * k, expected);
+        assert_eq!(p.to_affine() * k, expected);
+    }
+}
+
+#[test]
+fn test_decompose_scalar() {
+    use ff::{Field, PrimeField, WithSmallOrderMulGroup};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let signed = |v: u128, neg: bool| {
+        let v = Fq::from_u128(v);
+        if neg {
+            -v
+        } else {
+            v
+        }
+    };
+
+    for k in [-Fq::from(3), Fq::ZETA, Fq::random(&mut rng)] {
+        let (k1, k2, (k1_neg, k2_neg)) = decompose_scalar(&k);
+        assert_eq!(signed(k1, k1_neg) + signed(k2, k2_neg) * Fq::ZETA, k);
+    }
+    assert_eq!(decompose_scalar(&Fq::ZETA), (0, 1, (false, false)));
+}
+
+#[test]
+fn test_endo() {
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..10 {
+        let p = Ep::random(&mut rng);
+        assert_eq!(p.endo(), p * Ep::ZETA_SCALAR);
+        assert_eq!(p.endo().endo().endo(), p);
+        assert_eq!(
+            p.to_affine().endo(),
+            (p * EpAffine::ZETA_SCALAR).to_affine()
+        );
+    }
+    assert_eq!(Ep::identity().endo(), Ep::identity());
+    assert_eq!(EpAffine::identity().endo(), EpAffine::identity());
+}
+
+#[test]
+fn test_mul_generator() {
+    use ff::{Field, PrimeField};
+    use group::Group;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let g = Ep::generator();
+    let edge_cases = [
+        Fq::zero(),
+        Fq::one(),
+        -Fq::one(),
+        Fq::from(8),
+        -Fq::from(8),
+        Fq::TWO_INV,
+        // Scalars whose radix-16 digits are almost all zero.
+        Fq::from(16),
+        Fq::from_u128(1 << 124),
+        Fq::from_u128((8 << 120) | 1),
+    ];
+    for k in edge_cases
+        .into_iter()
+        .chain((0..100).map(|_| Fq::random(&mut rng)))
+    {
+        assert_eq!(Ep::mul_generator(&k), g * k);
+    }
+}
+
+#[test]
+fn test_multiscalar_mul_2() {
+    use ff::{Field, PrimeField};
+    use group::Group;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Ep::random(&mut rng);
+    let q = Ep::random(&mut rng);
+    let edge_cases = [
+        (Fq::zero(), p, Fq::zero(), q),
+        (Fq::one(), p, -Fq::one(), p),
+        (-Fq::one(), p, Fq::TWO_INV, Ep::identity()),
+        (Fq::from(3), p, Fq::from(5), p.double()),
+    ];
+    let random = (0..50).map(|_| {
+        (
+            Fq::random(&mut rng),
+            Ep::random(&mut rng),
+            Fq::random(&mut rng),
+            Ep::random(&mut rng),
+        )
+    });
+    for (a, p, b, q) in edge_cases.into_iter().chain(random) {
+        let expected = p * a + q * b;
+        assert_eq!(Ep::multiscalar_mul_2(&a, &p, &b, &q), expected);
+        assert_eq!(Ep::multiscalar_mul_2_vartime(&a, &p, &b, &q), expected);
+    }
+}
+
+#[test]
+fn test_incomplete_formulas() {
+    use group::{Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Ep::random(&mut rng);
+        let q = Ep::random(&mut rng);
+        assert_eq!(p.add_incomplete(&q), p + q);
+        assert_eq!(p.add_incomplete_affine(&q.to_affine()), p + q);
+        assert_eq!(p.double_incomplete(), p.double());
+    }
+}
+
+#[test]
+fn test_add_ct() {
+    use group::{Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Ep::random(&mut rng);
+    let q = Ep::random(&mut rng);
+    let id = Ep::identity();
+    for (a, b) in [
+        (p, q),
+        (p, p),
+        (p, -p),
+        (p, id),
+        (id, p),
+        (id, id),
+        (p, p.endo()),
+    ] {
+        assert_eq!(a.add_ct(&b), a + b);
+        assert_eq!(a.add_mixed_ct(&b.to_affine()), a + b);
+    }
+}
+
+#[test]
+fn test_affine_coordinates() {
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Ep::random(&mut rng).to_affine();
+        assert_eq!(EpAffine::from_xy(p.x(), p.y()).unwrap(), p);
+        assert_eq!(EpAffine::from_xy_unchecked(p.x(), p.y()), p);
+        assert!(bool::from(
+            EpAffine::from_xy(p.x(), p.y() + Fp::one()).is_none()
+        ));
+    }
+
+    let identity = EpAffine::identity();
+    assert_eq!((identity.x(), identity.y()), (Fp::zero(), Fp::zero()));
+    assert_eq!(EpAffine::from_xy(Fp::zero(), Fp::zero()).unwrap(), identity);
+}
+
+#[test]
+fn test_projective_coordinates() {
+    use ff::Field;
+    use group::{Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Ep::random(&mut rng).double();
+        let q = Ep::new_unchecked(p.x(), p.y(), p.z());
+        assert_eq!(q, p);
+
+        let z = p.z().invert().unwrap();
+        let affine = p.to_affine();
+        assert_eq!(p.x() * z.square(), affine.x());
+        assert_eq!(p.y() * z.square() * z, affine.y());
+    }
+
+    assert!(bool::from(Ep::identity().z().is_zero()));
+}
+
+#[test]
+fn test_validate() {
+    use crate::DecodeError;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Ep::random(&mut rng);
+    assert!(bool::from(p.is_on_curve()));
+    assert_eq!(p.validate(), Ok(()));
+    assert_eq!(Ep::identity().validate(), Ok(()));
+    let p = p.to_affine();
+    assert!(bool::from(p.is_on_curve()));
+    assert_eq!(p.validate(), Ok(()));
+    assert_eq!(EpAffine::identity().validate(), Ok(()));
+
+    let invalid = EpAffine::from_xy_unchecked(p.x(), p.y() + Fp::one());
+    assert!(!bool::from(invalid.is_on_curve()));
+    assert_eq!(invalid.validate(), Err(DecodeError::NotOnCurve));
+    let invalid = Ep::new_unchecked(p.x(), p.y() + Fp::one(), Fp::one());
+    assert!(!bool::from(invalid.is_on_curve()));
+    assert_eq!(invalid.validate(), Err(DecodeError::NotOnCurve));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_add() {
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::vec::Vec;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Ep::random(&mut rng).to_affine();
+    let q = Ep::random(&mut rng).to_affine();
+    let mut a = std::vec![p, p, p, EpAffine::identity(), p, EpAffine::identity()];
+    let mut b = std::vec![q, p, -p, q, EpAffine::identity(), EpAffine::identity()];
+    for _ in 0..100 {
+        a.push(Ep::random(&mut rng).to_affine());
+        b.push(Ep::random(&mut rng).to_affine());
+    }
+
+    let expected: Vec<_> = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| (a + b).to_affine())
+        .collect();
+    assert_eq!(EpAffine::batch_add(&a, &b), expected);
+}
+
+#[test]
+fn test_mul_by_pow_2() {
+    use ff::Field;
+    use group::Group;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Ep::random(&mut rng);
+    let mut expected = p;
+    for k in 0..300 {
+        assert_eq!(p.mul_by_pow_2(k), expected);
+        expected = expected.double();
+    }
+    assert_eq!(p.mul_by_pow_2(255), p * Fq::from(2).pow_vartime([255]));
+    assert!(bool::from(Ep::identity().mul_by_pow_2(10).is_identity()));
+}
+
+#[test]
+fn test_mul_u64() {
+    use ff::PrimeField;
+    use group::Group;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Ep::random(&mut rng);
+    for k in [0, 1, 2, 3, u64::MAX]
+        .into_iter()
+        .chain((0..20).map(|_| rng.gen()))
+    {
+        assert_eq!(p.mul_u64(k), p * Fq::from(k));
+    }
+    for k in [u128::MAX, 1 << 127]
+        .into_iter()
+        .chain((0..20).map(|_| (rng.gen::<u64>() as u128) << 64 | rng.gen::<u64>() as u128))
+    {
+        assert_eq!(p.mul_u128(k), p * Fq::from_u128(k));
+    }
+    assert!(bool::from(Ep::identity().mul_u64(5).is_identity()));
+}
+
+#[test]
+fn test_uncompressed_encoding() {
+    use crate::UncompressedPoint;
+    use ff::PrimeField;
+    use group::{prime::PrimeCurveAffine, Curve, Group, UncompressedEncoding};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Ep::random(&mut rng).to_affine();
+        let bytes = p.to_uncompressed();
+        assert_eq!(&bytes.0[..32], &p.x().to_repr());
+        assert_eq!(&bytes.0[32..], &p.y().to_repr());
+        assert_eq!(EpAffine::from_uncompressed(&bytes).unwrap(), p);
+        assert_eq!(EpAffine::from_uncompressed_unchecked(&bytes).unwrap(), p);
+
+        let mut invalid = bytes;
+        invalid.0[32] ^= 1;
+        assert!(bool::from(EpAffine::from_uncompressed(&invalid).is_none()));
+        assert!(bool::from(
+            EpAffine::from_uncompressed_unchecked(&invalid).is_some()
+        ));
+    }
+
+    let identity = EpAffine::identity().to_uncompressed();
+    assert_eq!(identity, UncompressedPoint::default());
+    assert_eq!(
+        EpAffine::from_uncompressed(&identity).unwrap(),
+        EpAffine::identity()
+    );
+    assert_eq!((Ep::identity().to_affine()).to_uncompressed(), identity);
+
+    let mut non_canonical = identity;
+    non_canonical.0[..32].fill(0xff);
+    assert!(bool::from(
+        EpAffine::from_uncompressed_unchecked(&non_canonical).is_none()
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_sec1_encoding() {
+    use crate::DecodeError;
+    use ff::PrimeField;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Ep::random(&mut rng).to_affine();
+        let mut x = p.x().to_repr();
+        x.reverse();
+
+        let compressed = p.to_sec1_bytes(true);
+        assert_eq!(compressed.len(), 33);
+        assert_eq!(compressed[0], 0x02 | p.y().is_odd().unwrap_u8());
+        assert_eq!(&compressed[1..], &x);
+        assert_eq!(EpAffine::from_sec1_bytes(&compressed), Ok(p));
+
+        let uncompressed = p.to_sec1_bytes(false);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(uncompressed[0], 0x04);
+        assert_eq!(&uncompressed[1..33], &x);
+        assert_eq!(EpAffine::from_sec1_bytes(&uncompressed), Ok(p));
+
+        let mut invalid = uncompressed;
+        invalid[64] ^= 1;
+        assert_eq!(
+            EpAffine::from_sec1_bytes(&invalid),
+            Err(DecodeError::NotOnCurve)
+        );
+    }
+
+    let identity = EpAffine::identity();
+    assert_eq!(identity.to_sec1_bytes(true), [0x00]);
+    assert_eq!(identity.to_sec1_bytes(false), [0x00]);
+    assert_eq!(EpAffine::from_sec1_bytes(&[0x00]), Ok(identity));
+    let mut zero = [0u8; 65];
+    zero[0] = 0x04;
+    assert_eq!(
+        EpAffine::from_sec1_bytes(&zero),
+        Err(DecodeError::NotOnCurve)
+    );
+
+    assert_eq!(
+        EpAffine::from_sec1_bytes(&[]),
+        Err(DecodeError::InvalidHeader)
+    );
+    assert_eq!(
+        EpAffine::from_sec1_bytes(&[0x05; 33]),
+        Err(DecodeError::InvalidHeader)
+    );
+    assert_eq!(
+        EpAffine::from_sec1_bytes(&[0x02; 65]),
+        Err(DecodeError::InvalidLength {
+            expected: 33,
+            actual: 65
+        })
+    );
+    assert_eq!(
+        EpAffine::from_sec1_bytes(&[0x00, 0x00]),
+        Err(DecodeError::InvalidLength {
+            expected: 1,
+            actual: 2
+        })
+    );
+    let mut non_canonical = [0xff; 33];
+    non_canonical[0] = 0x02;
+    assert_eq!(
+        EpAffine::from_sec1_bytes(&non_canonical),
+        Err(DecodeError::NonCanonical)
+    );
+}
+
+#[test]
+fn test_from_bytes_reject_identity() {
+    use group::{Curve, Group, GroupEncoding};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Ep::random(&mut rng);
+    let bytes = p.to_bytes();
+    assert_eq!(Ep::from_bytes_reject_identity(&bytes).unwrap(), p);
+    assert_eq!(
+        EpAffine::from_bytes_reject_identity(&bytes).unwrap(),
+        p.to_affine()
+    );
+
+    let identity = Ep::identity().to_bytes();
+    assert_eq!(identity, [0; 32]);
+    assert!(bool::from(Ep::from_bytes(&identity).is_some()));
+    assert!(bool::from(
+        Ep::from_bytes_reject_identity(&identity).is_none()
+    ));
+    assert!(bool::from(
+        EpAffine::from_bytes_reject_identity(&identity).is_none()
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_from_bytes() {
+    use group::{Curve, Group, GroupEncoding};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::vec::Vec;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut bytes: Vec<[u8; 32]> = (0..100).map(|_| Ep::random(&mut rng).to_bytes()).collect();
+    bytes[10] = Ep::identity().to_bytes();
+    bytes[20] = [0xff; 32];
+
+    let points = EpAffine::batch_from_bytes(&bytes);
+    assert_eq!(points.len(), bytes.len());
+    for (p, bytes) in points.iter().zip(bytes.iter()) {
+        let expected = EpAffine::from_bytes(bytes);
+        assert_eq!(bool::from(p.is_some()), bool::from(expected.is_some()));
+        if let Some(p) = Option::<EpAffine>::from(*p) {
+            assert_eq!(p, expected.unwrap());
+            assert_eq!(p.to_bytes(), *bytes);
+        }
+    }
+    assert!(bool::from(points[20].is_none()));
+    assert_eq!(points[10].unwrap(), Ep::identity().to_affine());
+}
+
+#[test]
+fn test_y_parity() {
+    use group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use subtle::Choice;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Ep::random(&mut rng).to_affine();
+        assert_eq!(p.y_is_odd().unwrap_u8(), p.to_bytes()[31] >> 7);
+        assert_eq!(EpAffine::from_x_and_parity(p.x(), p.y_is_odd()).unwrap(), p);
+        assert_eq!(
+            EpAffine::from_x_and_parity(p.x(), !p.y_is_odd()).unwrap(),
+            -p
+        );
+    }
+    assert!(!bool::from(EpAffine::identity().y_is_odd()));
+
+    let x = Ep::random(&mut rng).to_affine().x();
+    assert!(bool::from(
+        EpAffine::from_x_and_parity(x, Choice::from(0)).is_some()
+    ));
+    // About half of all x-coordinates are not on the curve.
+    let mut x = x;
+    while bool::from(EpAffine::from_x_and_parity(x, Choice::from(0)).is_some()) {
+        x += Fp::one();
+    }
+    assert!(bool::from(
+        EpAffine::from_x_and_parity(x, Choice::from(1)).is_none()
+    ));
+}
+
+#[test]
+fn test_curve_constants() {
+    use ff::Field;
+
+    assert_eq!(Ep::A, Fp::zero());
+    assert_eq!(Ep::B, Fp::from(5));
+    assert_eq!(Ep::j_invariant(), Fp::zero());
+    assert_eq!(Ep::ORDER, Fq::MODULUS_LIMBS);
+    let mut order_minus_one = Ep::ORDER;
+    order_minus_one[0] -= 1;
+    assert_eq!(Fq::from_raw(order_minus_one), -Fq::one());
+
+    assert!(!bool::from(Ep::ISO_A.is_zero()));
+    assert!(!bool::from(Ep::ISO_B.is_zero()));
+    assert!(!bool::from(crate::curves::IsoEp::j_invariant().is_zero()));
+}
@@ -0,0 +1,236 @@
+//! Support for zero-copy archives with `rkyv`.
+//!
+//! Field elements and points are archived as their canonical 32-byte encodings, so
+//! archives are independent of the target's endianness and have an alignment of one.
+//!
+//! With the `rkyv-validation` feature flag, the archived types implement
+//! [`CheckBytes`](rkyv::bytecheck::CheckBytes), which rejects encodings that are not
+//! canonical, so that archives from untrusted sources can be validated with
+//! `rkyv::check_archived_root`.
+
+use ff::PrimeField;
+use group::{Curve, GroupEncoding};
+use rkyv::{Archive, Deserialize, Fallible, Serialize};
+use subtle::CtOption;
+
+#[cfg(feature = "rkyv-validation")]
+use crate::DecodeError;
+use crate::{
+    curves::{Ep, EpAffine, Eq, EqAffine},
+    fields::{Fp, Fq},
+};
+
+macro_rules! archived_type {
+    ($archived:ident, $name:ident, $desc:literal, $decode:expr, $validate:expr) => {
+        #[doc = concat!("An archived [`", stringify!($name), "`], stored as its canonical")]
+        /// 32-byte encoding.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        pub struct $archived([u8; 32]);
+
+        impl $archived {
+            #[doc = concat!("Returns the archived ", $desc, ".")]
+            ///
+            /// This fails if the archived bytes are not a canonical encoding, which can
+            /// only happen if the archive is corrupt and was accessed without validation.
+            pub fn get(&self) -> CtOption<$name> {
+                $decode(&self.0)
+            }
+
+            /// Returns the archived canonical encoding.
+            pub fn as_bytes(&self) -> &[u8; 32] {
+                &self.0
+            }
+        }
+
+        #[cfg(feature = "rkyv-validation")]
+        #[allow(unsafe_code)]
+        impl<C: ?Sized> rkyv::bytecheck::CheckBytes<C> for $archived {
+            type Error = DecodeError;
+
+            unsafe fn check_bytes<'a>(
+                value: *const Self,
+                _: &mut C,
+            ) -> Result<&'a Self, DecodeError> {
+                // Any 32 bytes are a valid `[u8; 32]`, and the alignment is one.
+                let value = &*value;
+                $validate(&value.0).map(|_| value)
+            }
+        }
+
+        impl<D: Fallible + ?Sized> Deserialize<$name, D> for $archived {
+            /// Deserializes the archived value.
+            ///
+            /// Archives that were validated with `CheckBytes` only contain canonical
+            /// encodings. If an unvalidated archive contains an encoding that is not
+            /// canonical, this returns the default value rather than panicking.
+            fn deserialize(&self, _: &mut D) -> Result<$name, D::Error> {
+                Ok(self.get().unwrap_or($name::default()))
+            }
+        }
+    };
+}
+
+macro_rules! impl_archive {
+    ($name:ident, $archived:ident, $encode:expr) => {
+        #[allow(unsafe_code)]
+        impl Archive for $name {
+            type Archived = $archived;
+            type Resolver = ();
+
+            unsafe fn resolve(&self, _: usize, _: (), out: *mut $archived) {
+                out.write($archived($encode(self)));
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for $name {
+            fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+                Ok(())
+            }
+        }
+    };
+}
+
+archived_type!(
+    ArchivedFp,
+    Fp,
+    "Pallas field element",
+    |b: &[u8; 32]| Fp::from_repr(*b),
+    |b: &[u8; 32]| Fp::try_from_repr(*b)
+);
+archived_type!(
+    ArchivedFq,
+    Fq,
+    "Vesta field element",
+    |b: &[u8; 32]| Fq::from_repr(*b),
+    |b: &[u8; 32]| Fq::try_from_repr(*b)
+);
+archived_type!(
+    ArchivedEpAffine,
+    EpAffine,
+    "Pallas curve point",
+    EpAffine::from_bytes,
+    EpAffine::try_from_bytes
+);
+archived_type!(
+    ArchivedEqAffine,
+    EqAffine,
+    "Vesta curve point",
+    EqAffine::from_bytes,
+    EqAffine::try_from_bytes
+);
+
+impl_archive!(Fp, ArchivedFp, Fp::to_repr);
+impl_archive!(Fq, ArchivedFq, Fq::to_repr);
+impl_archive!(EpAffine, ArchivedEpAffine, EpAffine::to_bytes);
+impl_archive!(EqAffine, ArchivedEqAffine, EqAffine::to_bytes);
+
+// Projective points are archived in affine form.
+impl_archive!(Ep, ArchivedEpAffine, |p: &Ep| p.to_affine().to_bytes());
+impl_archive!(Eq, ArchivedEqAffine, |p: &Eq| p.to_affine().to_bytes());
+
+impl<D: Fallible + ?Sized> Deserialize<Ep, D> for ArchivedEpAffine {
+    fn deserialize(&self, d: &mut D) -> Result<Ep, D::Error> {
+        Deserialize::<EpAffine, D>::deserialize(self, d).map(Ep::from)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Eq, D> for ArchivedEqAffine {
+    fn deserialize(&self, d: &mut D) -> Result<Eq, D::Error> {
+        Deserialize::<EqAffine, D>::deserialize(self, d).map(Eq::from)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use core::fmt::Debug;
+
+    use ff::Field;
+    use group::{prime::PrimeCurveAffine, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use rkyv::ser::serializers::AllocSerializer;
+
+    #[allow(unsafe_code)]
+    fn test_roundtrip<T, A>(t: &T, expected: A)
+    where
+        T: Archive<Archived = A> + Serialize<AllocSerializer<64>> + Debug + PartialEq,
+        A: Deserialize<T, rkyv::Infallible> + Debug + PartialEq,
+    {
+        let bytes = rkyv::to_bytes::<_, 64>(t).unwrap();
+        let archived = unsafe { rkyv::archived_root::<T>(&bytes[..]) };
+        assert_eq!(*archived, expected);
+        assert_eq!(archived.deserialize(&mut rkyv::Infallible).unwrap(), *t);
+    }
+
+    #[test]
+    fn rkyv_roundtrip() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for _ in 0..20 {
+            let a = Fp::random(&mut rng);
+            test_roundtrip(&a, ArchivedFp(a.to_repr()));
+            let b = Fq::random(&mut rng);
+            test_roundtrip(&b, ArchivedFq(b.to_repr()));
+
+            let p = Ep::random(&mut rng);
+            test_roundtrip(&p, ArchivedEpAffine(p.to_bytes()));
+            test_roundtrip(&p.to_affine(), ArchivedEpAffine(p.to_bytes()));
+            let q = Eq::random(&mut rng);
+            test_roundtrip(&q, ArchivedEqAffine(q.to_bytes()));
+            test_roundtrip(&q.to_affine(), ArchivedEqAffine(q.to_bytes()));
+        }
+
+        test_roundtrip(&EpAffine::identity(), ArchivedEpAffine([0; 32]));
+        test_roundtrip(&EqAffine::identity(), ArchivedEqAffine([0; 32]));
+    }
+
+    #[test]
+    fn rkyv_invalid() {
+        assert!(bool::from(ArchivedFp([0xff; 32]).get().is_none()));
+        assert!(bool::from(ArchivedFq([0xff; 32]).get().is_none()));
+        assert!(bool::from(ArchivedEpAffine([0xff; 32]).get().is_none()));
+        assert!(bool::from(ArchivedEqAffine([0xff; 32]).get().is_none()));
+        assert_eq!(ArchivedFp([0; 32]).get().unwrap(), Fp::zero());
+
+        // Non-canonical encodings deserialize to the default value.
+        let archived = ArchivedEpAffine([0xff; 32]);
+        assert_eq!(
+            Deserialize::<EpAffine, _>::deserialize(&archived, &mut rkyv::Infallible).unwrap(),
+            EpAffine::identity()
+        );
+    }
+
+    #[cfg(feature = "rkyv-validation")]
+    #[test]
+    fn rkyv_check_bytes() {
+        use crate::DecodeError;
+
+        let bytes = rkyv::to_bytes::<_, 64>(&Fp::one()).unwrap();
+        let archived = rkyv::check_archived_root::<Fp>(&bytes[..]).unwrap();
+        assert_eq!(archived.get().unwrap(), Fp::one());
+        let bytes = rkyv::to_bytes::<_, 64>(&Eq::generator()).unwrap();
+        let archived = rkyv::check_archived_root::<Eq>(&bytes[..]).unwrap();
+        assert_eq!(archived.get().unwrap(), EqAffine::generator());
+
+        let invalid = [0xff; 32];
+        assert!(rkyv::check_archived_root::<Fp>(&invalid).is_err());
+        assert!(rkyv::check_archived_root::<Fq>(&invalid).is_err());
+        assert!(rkyv::check_archived_root::<EpAffine>(&invalid).is_err());
+        assert!(rkyv::check_archived_root::<Eq>(&invalid).is_err());
+
+        // An x-coordinate for which there is no point on the curve.
+        let mut off_curve = [0; 32];
+        off_curve[0] = 2;
+        assert_eq!(
+            EpAffine::try_from_bytes(&off_curve),
+            Err(DecodeError::NotOnCurve)
+        );
+        assert!(rkyv::check_archived_root::<EpAffine>(&off_curve).is_err());
+    }
+}
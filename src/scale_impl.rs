@@ -0,0 +1,164 @@
+use ff::PrimeField;
+use group::GroupEncoding;
+use parity_scale_codec::{Decode, Encode, EncodeLike, Error, Input, MaxEncodedLen, Output};
+
+use crate::{
+    curves::{EpAffine, EqAffine},
+    fields::{Fp, Fq},
+};
+
+impl Encode for Fp {
+    fn size_hint(&self) -> usize {
+        32
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.to_repr());
+    }
+}
+
+impl EncodeLike for Fp {}
+
+impl MaxEncodedLen for Fp {
+    fn max_encoded_len() -> usize {
+        32
+    }
+}
+
+impl Decode for Fp {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let bytes = <[u8; 32]>::decode(input)?;
+        Option::from(Fp::from_repr(bytes))
+            .ok_or_else(|| "decoded bytes don't encode a Pallas field element".into())
+    }
+}
+
+impl Encode for Fq {
+    fn size_hint(&self) -> usize {
+        32
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.to_repr());
+    }
+}
+
+impl EncodeLike for Fq {}
+
+impl MaxEncodedLen for Fq {
+    fn max_encoded_len() -> usize {
+        32
+    }
+}
+
+impl Decode for Fq {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let bytes = <[u8; 32]>::decode(input)?;
+        Option::from(Fq::from_repr(bytes))
+            .ok_or_else(|| "decoded bytes don't encode a Vesta field element".into())
+    }
+}
+
+impl Encode for EpAffine {
+    fn size_hint(&self) -> usize {
+        32
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.to_bytes());
+    }
+}
+
+impl EncodeLike for EpAffine {}
+
+impl MaxEncodedLen for EpAffine {
+    fn max_encoded_len() -> usize {
+        32
+    }
+}
+
+impl Decode for EpAffine {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let bytes = <[u8; 32]>::decode(input)?;
+        Option::from(EpAffine::from_bytes(&bytes))
+            .ok_or_else(|| "decoded bytes don't encode a Pallas curve point".into())
+    }
+}
+
+impl Encode for EqAffine {
+    fn size_hint(&self) -> usize {
+        32
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.to_bytes());
+    }
+}
+
+impl EncodeLike for EqAffine {}
+
+impl MaxEncodedLen for EqAffine {
+    fn max_encoded_len() -> usize {
+        32
+    }
+}
+
+impl Decode for EqAffine {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let bytes = <[u8; 32]>::decode(input)?;
+        Option::from(EqAffine::from_bytes(&bytes))
+            .ok_or_else(|| "decoded bytes don't encode a Vesta curve point".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::fmt::Debug;
+
+    use ff::Field;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use parity_scale_codec::DecodeAll;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    use crate::curves::{Ep, Eq};
+
+    fn test_roundtrip<T: Encode + Decode + MaxEncodedLen + Debug + PartialEq>(t: &T) {
+        let encoded = t.encode();
+        assert_eq!(encoded.len(), T::max_encoded_len());
+        assert_eq!(encoded.len(), t.size_hint());
+        assert_eq!(*t, T::decode_all(&mut &encoded[..]).unwrap());
+    }
+
+    #[test]
+    fn scale_roundtrip() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for _ in 0..100 {
+            test_roundtrip(&Fp::random(&mut rng));
+            test_roundtrip(&Fq::random(&mut rng));
+            test_roundtrip(&Ep::random(&mut rng).to_affine());
+            test_roundtrip(&Eq::random(&mut rng).to_affine());
+        }
+        test_roundtrip(&EpAffine::identity());
+        test_roundtrip(&EqAffine::identity());
+
+        assert_eq!(Fp::one().encode(), Fp::one().to_repr());
+    }
+
+    #[test]
+    fn scale_invalid() {
+        assert!(Fp::decode(&mut &[0xff; 32][..]).is_err());
+        assert!(Fq::decode(&mut &[0xff; 32][..]).is_err());
+        assert!(EpAffine::decode(&mut &[0xff; 32][..]).is_err());
+        assert!(EqAffine::decode(&mut &[0xff; 32][..]).is_err());
+
+        // Truncated input.
+        assert!(Fp::decode(&mut &[0; 31][..]).is_err());
+    }
+}
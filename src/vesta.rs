@@ -14,6 +14,21 @@ pub type Point = Eq;
 /// A Vesta point in the affine coordinate space (or the point at infinity).
 pub type Affine = EqAffine;
 
+/// Decomposes `k` for the Vesta endomorphism `(x, y) -> (Base::ZETA * x, y)`, which
+/// maps each point `P` to `[Scalar::ZETA] P`.
+///
+/// Returns `(k1, k2, (k1_neg, k2_neg))` such that `k = ±k1 ± k2 * Scalar::ZETA`,
+/// where each sign is negative if the corresponding flag is set. This is the
+/// decomposition used by [`Eq::mul_glv`], and it is computed in constant time.
+pub fn decompose_scalar(k: &Scalar) -> (u128, u128, (bool, bool)) {
+    let (k1, k1_neg, k2, k2_neg) = k.glv_decompose();
+    (
+        crate::glv::to_u128(&k1),
+        crate::glv::to_u128(&k2),
+        (k1_neg.into(), k2_neg.into()),
+    )
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 fn test_map_to_curve_simple_swu() {
@@ -70,3 +85,824 @@ fn test_hash_to_curve() {
         format!("{:?}", z) == "0x1b58d4aa4d68c3f4d9916b77c79ff9911597a27f2ee46244e98eb9615172d2ad"
     );
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_hash_to_curve_with_hash() {
+    use crate::arithmetic::CurveExt;
+    use crate::hashtocurve::Blake2b512;
+
+    let message = b"Trans rights now!";
+    assert_eq!(
+        Eq::hash_to_curve_with_hash::<Blake2b512>("z.cash:test")(message),
+        Eq::hash_to_curve("z.cash:test")(message)
+    );
+}
+
+#[cfg(feature = "hash-to-curve-sha2")]
+#[test]
+fn test_hash_to_curve_sha256() {
+    use group::Curve;
+
+    // Computed with scripts/hash_to_curve_vectors.py.
+    assert_eq!(Eq::SHA256_SUITE_ID, "vesta_XMD:SHA-256_SSWU_RO_");
+    let hash = Eq::hash_to_curve_sha256(b"QUUX-V01-CS02-with-vesta_XMD:SHA-256_SSWU_RO_");
+    let vectors: [(&[u8], &str, &str); 3] = [
+        (
+            b"",
+            "0x03dd8ee421e44e89c2c088cb46505002e32f8c6566f1d2583a7973d6d69d418d",
+            "0x182ec24db0070de1291b930f0e46aa847a0b7e4a006befba43cafe1a700d0a26",
+        ),
+        (
+            b"abc",
+            "0x39d1ec3185cd0d9923c8c8f510be92501c2209872e88ffcbb61a13ea5ef6a26b",
+            "0x115e8945aa1a8c7d829773b34e559915980533dc3f7e70711465f6776ed4e8bf",
+        ),
+        (
+            b"abcdef0123456789",
+            "0x3e04cc434a78eb6d55ee0b6bd738acfdb8e81a770126bb42a90323e7acce3354",
+            "0x0af9003666f04668098993bbdf662aa20c1e8afb08815ea4484c79f58240fc75",
+        ),
+    ];
+    for (message, x, y) in vectors {
+        let p = hash(message).to_affine();
+        assert_eq!(format!("{:?}", p.x()), x);
+        assert_eq!(format!("{:?}", p.y()), y);
+    }
+}
+
+#[cfg(feature = "hash-to-curve-sha3")]
+#[test]
+fn test_hash_to_curve_shake128() {
+    use crate::hashtocurve::{ExpandMsgXof, Shake128};
+    use group::Curve;
+
+    // Computed with scripts/hash_to_curve_vectors.py.
+    let hash = Eq::hash_to_curve_with::<ExpandMsgXof<Shake128>>(
+        b"QUUX-V01-CS02-with-vesta_XOF:SHAKE128_SSWU_RO_",
+    );
+    let vectors: [(&[u8], &str, &str); 2] = [
+        (
+            b"",
+            "0x189312f6389e3e0d832e7f02f69df8cd42b9296b21026f56fc06137067934683",
+            "0x3df81f1e0f767c1ad850dd821e33ccf851cb7503e3679bbd67dbc6850a5aadcd",
+        ),
+        (
+            b"abc",
+            "0x3d99246a4882e11ca4e05e29fe8fe6e4961c5ced6b052a2ccc5cc257a2a8fcf2",
+            "0x387207ca8264c72ced30e64ee1bcf2769e310b597b19d48b2f496f20a0917fef",
+        ),
+    ];
+    for (message, x, y) in vectors {
+        let p = hash(message).to_affine();
+        assert_eq!(format!("{:?}", p.x()), x);
+        assert_eq!(format!("{:?}", p.y()), y);
+    }
+}
+
+#[test]
+fn test_hex() {
+    use group::{prime::PrimeCurveAffine, GroupEncoding};
+    use std::string::String;
+
+    let g = EqAffine::generator();
+    let bytes = g.to_bytes();
+    let lower: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    let upper: String = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+    assert_eq!(format!("{:x}", g), lower);
+    assert_eq!(format!("{:X}", g), upper);
+    assert_eq!(format!("{:#x}", g), format!("0x{}", lower));
+    assert_eq!(format!("{:x}", EqAffine::identity()), "0".repeat(64));
+}
+
+#[test]
+fn test_sum() {
+    use group::{Curve, Group};
+
+    let g = Eq::generator();
+    let points = [g, g.double(), -g, g * Fp::from(5)];
+    let affine: std::vec::Vec<EqAffine> = points.iter().map(|p| p.to_affine()).collect();
+    let expected = g * Fp::from(7);
+
+    assert_eq!(points.iter().sum::<Eq>(), expected);
+    assert_eq!(points.iter().copied().sum::<Eq>(), expected);
+    assert_eq!(affine.iter().sum::<Eq>(), expected);
+    assert_eq!(affine.into_iter().sum::<Eq>(), expected);
+    assert_eq!(core::iter::empty::<EqAffine>().sum::<Eq>(), Eq::identity());
+}
+
+#[test]
+fn test_try_from_bytes() {
+    use crate::DecodeError;
+    use group::{prime::PrimeCurveAffine, GroupEncoding};
+
+    let g = EqAffine::generator();
+    assert_eq!(EqAffine::try_from(&g.to_bytes()[..]), Ok(g));
+    assert_eq!(
+        EqAffine::try_from(&EqAffine::identity().to_bytes()[..]),
+        Ok(EqAffine::identity())
+    );
+
+    assert_eq!(
+        EqAffine::try_from(&g.to_bytes()[1..]),
+        Err(DecodeError::InvalidLength {
+            expected: 32,
+            actual: 31
+        })
+    );
+    assert_eq!(
+        EqAffine::try_from(&[0xff; 32][..]),
+        Err(DecodeError::NonCanonical)
+    );
+
+    // The compression flag must not be set for the identity.
+    let mut bytes = [0u8; 32];
+    bytes[31] = 0x80;
+    assert_eq!(
+        EqAffine::try_from_bytes(&bytes),
+        Err(DecodeError::InvalidCompressionFlag)
+    );
+
+    // Find an x-coordinate for which x^3 + 5 is not a square.
+    let mut bytes = [0u8; 32];
+    bytes[0] = 1;
+    while bool::from(EqAffine::from_bytes(&bytes).is_some()) {
+        bytes[0] += 1;
+    }
+    assert_eq!(
+        EqAffine::try_from_bytes(&bytes),
+        Err(DecodeError::NotOnCurve)
+    );
+}
+
+#[test]
+fn test_hash() {
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use std::collections::HashSet;
+
+    let g = EqAffine::generator();
+    let points = [EqAffine::identity(), g, (g + g).to_affine(), -g];
+    let set: HashSet<EqAffine> = points.iter().chain(points.iter()).copied().collect();
+    assert_eq!(set.len(), points.len());
+    assert!(points.iter().all(|p| set.contains(p)));
+    assert!(!set.contains(&(Eq::generator() * Fp::from(3)).to_affine()));
+}
+
+#[test]
+fn test_ord() {
+    use group::{prime::PrimeCurveAffine, Curve, GroupEncoding};
+    use std::collections::BTreeSet;
+
+    let g = EqAffine::generator();
+    let points = [EqAffine::identity(), g, (g + g).to_affine(), -g];
+    let set: BTreeSet<EqAffine> = points.iter().chain(points.iter()).copied().collect();
+    assert_eq!(set.len(), points.len());
+    assert!(set
+        .iter()
+        .zip(set.iter().skip(1))
+        .all(|(a, b)| a.to_bytes() < b.to_bytes()));
+    assert_eq!(set.iter().next(), Some(&EqAffine::identity()));
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_zeroize() {
+    use group::{prime::PrimeCurveAffine, Group};
+    use zeroize::Zeroize;
+
+    let mut a = EqAffine::generator();
+    a.zeroize();
+    assert!(bool::from(a.is_identity()));
+
+    let mut a = Eq::generator();
+    a.zeroize();
+    assert!(bool::from(a.is_identity()));
+}
+
+#[test]
+fn test_distribution() {
+    use group::Group;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    let seed = [
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ];
+    let a: Eq = XorShiftRng::from_seed(seed).gen();
+    let b = Eq::random(XorShiftRng::from_seed(seed));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_mul_glv() {
+    use ff::{Field, PrimeField, WithSmallOrderMulGroup};
+    use group::{Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn double_and_add(p: &Eq, k: &Fp) -> Eq {
+        k.to_repr()
+            .iter()
+            .rev()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+            .fold(Eq::identity(), |acc, bit| {
+                if bit {
+                    acc.double() + p
+                } else {
+                    acc.double()
+                }
+            })
+    }
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for k in [Fp::zero(), Fp::one(), -Fp::one(), Fp::ZETA] {
+        assert_eq!(
+            Eq::generator().mul_glv(&k),
+            double_and_add(&Eq::generator(), &k)
+        );
+    }
+    assert_eq!(
+        Eq::identity().mul_glv(&Fp::random(&mut rng)),
+        Eq::identity()
+    );
+
+    for _ in 0..100 {
+        let p = Eq::random(&mut rng);
+        let k = Fp::random(&mut rng);
+        let expected = double_and_add(&p, &k);
+        assert_eq!(p.mul_glv(&k), expected);
+        assert_eq!(p * k, expected);
+        assert_eq!(p.to_affine() * k, expected);
+    }
+}
+
+#[test]
+fn test_decompose_scalar() {
+    use ff::{Field, PrimeField, WithSmallOrderMulGroup};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let signed = |v: u128, neg: bool| {
+        let v = Fp::from_u128(v);
+        if neg {
+            -v
+        } else {
+            v
+        }
+    };
+
+    for k in [-Fp::from(3), Fp::ZETA, Fp::random(&mut rng)] {
+        let (k1, k2, (k1_neg, k2_neg)) = decompose_scalar(&k);
+        assert_eq!(signed(k1, k1_neg) + signed(k2, k2_neg) * Fp::ZETA, k);
+    }
+    assert_eq!(decompose_scalar(&Fp::ZETA), (0, 1, (false, false)));
+}
+
+#[test]
+fn test_endo() {
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..10 {
+        let p = Eq::random(&mut rng);
+        assert_eq!(p.endo(), p * Eq::ZETA_SCALAR);
+        assert_eq!(p.endo().endo().endo(), p);
+        assert_eq!(
+            p.to_affine().endo(),
+            (p * EqAffine::ZETA_SCALAR).to_affine()
+        );
+    }
+    assert_eq!(Eq::identity().endo(), Eq::identity());
+    assert_eq!(EqAffine::identity().endo(), EqAffine::identity());
+}
+
+#[test]
+fn test_mul_generator() {
+    use ff::{Field, PrimeField};
+    use group::Group;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let g = Eq::generator();
+    let edge_cases = [
+        Fp::zero(),
+        Fp::one(),
+        -Fp::one(),
+        Fp::from(8),
+        -Fp::from(8),
+        Fp::TWO_INV,
+        // Scalars whose radix-16 digits are almost all zero.
+        Fp::from(16),
+        Fp::from_u128(1 << 124),
+        Fp::from_u128((8 << 120) | 1),
+    ];
+    for k in edge_cases
+        .into_iter()
+        .chain((0..100).map(|_| Fp::random(&mut rng)))
+    {
+        assert_eq!(Eq::mul_generator(&k), g * k);
+    }
+}
+
+#[test]
+fn test_multiscalar_mul_2() {
+    use ff::{Field, PrimeField};
+    use group::Group;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Eq::random(&mut rng);
+    let q = Eq::random(&mut rng);
+    let edge_cases = [
+        (Fp::zero(), p, Fp::zero(), q),
+        (Fp::one(), p, -Fp::one(), p),
+        (-Fp::one(), p, Fp::TWO_INV, Eq::identity()),
+        (Fp::from(3), p, Fp::from(5), p.double()),
+    ];
+    let random = (0..50).map(|_| {
+        (
+            Fp::random(&mut rng),
+            Eq::random(&mut rng),
+            Fp::random(&mut rng),
+            Eq::random(&mut rng),
+        )
+    });
+    for (a, p, b, q) in edge_cases.into_iter().chain(random) {
+        let expected = p * a + q * b;
+        assert_eq!(Eq::multiscalar_mul_2(&a, &p, &b, &q), expected);
+        assert_eq!(Eq::multiscalar_mul_2_vartime(&a, &p, &b, &q), expected);
+    }
+}
+
+#[test]
+fn test_incomplete_formulas() {
+    use group::{Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Eq::random(&mut rng);
+        let q = Eq::random(&mut rng);
+        assert_eq!(p.add_incomplete(&q), p + q);
+        assert_eq!(p.add_incomplete_affine(&q.to_affine()), p + q);
+        assert_eq!(p.double_incomplete(), p.double());
+    }
+}
+
+#[test]
+fn test_add_ct() {
+    use group::{Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Eq::random(&mut rng);
+    let q = Eq::random(&mut rng);
+    let id = Eq::identity();
+    for (a, b) in [
+        (p, q),
+        (p, p),
+        (p, -p),
+        (p, id),
+        (id, p),
+        (id, id),
+        (p, p.endo()),
+    ] {
+        assert_eq!(a.add_ct(&b), a + b);
+        assert_eq!(a.add_mixed_ct(&b.to_affine()), a + b);
+    }
+}
+
+#[test]
+fn test_affine_coordinates() {
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Eq::random(&mut rng).to_affine();
+        assert_eq!(EqAffine::from_xy(p.x(), p.y()).unwrap(), p);
+        assert_eq!(EqAffine::from_xy_unchecked(p.x(), p.y()), p);
+        assert!(bool::from(
+            EqAffine::from_xy(p.x(), p.y() + Fq::one()).is_none()
+        ));
+    }
+
+    let identity = EqAffine::identity();
+    assert_eq!((identity.x(), identity.y()), (Fq::zero(), Fq::zero()));
+    assert_eq!(EqAffine::from_xy(Fq::zero(), Fq::zero()).unwrap(), identity);
+}
+
+#[test]
+fn test_projective_coordinates() {
+    use ff::Field;
+    use group::{Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Eq::random(&mut rng).double();
+        let q = Eq::new_unchecked(p.x(), p.y(), p.z());
+        assert_eq!(q, p);
+
+        let z = p.z().invert().unwrap();
+        let affine = p.to_affine();
+        assert_eq!(p.x() * z.square(), affine.x());
+        assert_eq!(p.y() * z.square() * z, affine.y());
+    }
+
+    assert!(bool::from(Eq::identity().z().is_zero()));
+}
+
+#[test]
+fn test_validate() {
+    use crate::DecodeError;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Eq::random(&mut rng);
+    assert!(bool::from(p.is_on_curve()));
+    assert_eq!(p.validate(), Ok(()));
+    assert_eq!(Eq::identity().validate(), Ok(()));
+    let p = p.to_affine();
+    assert!(bool::from(p.is_on_curve()));
+    assert_eq!(p.validate(), Ok(()));
+    assert_eq!(EqAffine::identity().validate(), Ok(()));
+
+    let invalid = EqAffine::from_xy_unchecked(p.x(), p.y() + Fq::one());
+    assert!(!bool::from(invalid.is_on_curve()));
+    assert_eq!(invalid.validate(), Err(DecodeError::NotOnCurve));
+    let invalid = Eq::new_unchecked(p.x(), p.y() + Fq::one(), Fq::one());
+    assert!(!bool::from(invalid.is_on_curve()));
+    assert_eq!(invalid.validate(), Err(DecodeError::NotOnCurve));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_add() {
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::vec::Vec;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Eq::random(&mut rng).to_affine();
+    let q = Eq::random(&mut rng).to_affine();
+    let mut a = std::vec![p, p, p, EqAffine::identity(), p, EqAffine::identity()];
+    let mut b = std::vec![q, p, -p, q, EqAffine::identity(), EqAffine::identity()];
+    for _ in 0..100 {
+        a.push(Eq::random(&mut rng).to_affine());
+        b.push(Eq::random(&mut rng).to_affine());
+    }
+
+    let expected: Vec<_> = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| (a + b).to_affine())
+        .collect();
+    assert_eq!(EqAffine::batch_add(&a, &b), expected);
+}
+
+#[test]
+fn test_mul_by_pow_2() {
+    use ff::Field;
+    use group::Group;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Eq::random(&mut rng);
+    let mut expected = p;
+    for k in 0..300 {
+        assert_eq!(p.mul_by_pow_2(k), expected);
+        expected = expected.double();
+    }
+    assert_eq!(p.mul_by_pow_2(255), p * Fp::from(2).pow_vartime([255]));
+    assert!(bool::from(Eq::identity().mul_by_pow_2(10).is_identity()));
+}
+
+#[test]
+fn test_mul_u64() {
+    use ff::PrimeField;
+    use group::Group;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Eq::random(&mut rng);
+    for k in [0, 1, 2, 3, u64::MAX]
+        .into_iter()
+        .chain((0..20).map(|_| rng.gen()))
+    {
+        assert_eq!(p.mul_u64(k), p * Fp::from(k));
+    }
+    for k in [u128::MAX, 1 << 127]
+        .into_iter()
+        .chain((0..20).map(|_| (rng.gen::<u64>() as u128) << 64 | rng.gen::<u64>() as u128))
+    {
+        assert_eq!(p.mul_u128(k), p * Fp::from_u128(k));
+    }
+    assert!(bool::from(Eq::identity().mul_u64(5).is_identity()));
+}
+
+#[test]
+fn test_uncompressed_encoding() {
+    use crate::UncompressedPoint;
+    use ff::PrimeField;
+    use group::{prime::PrimeCurveAffine, Curve, Group, UncompressedEncoding};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Eq::random(&mut rng).to_affine();
+        let bytes = p.to_uncompressed();
+        assert_eq!(&bytes.0[..32], &p.x().to_repr());
+        assert_eq!(&bytes.0[32..], &p.y().to_repr());
+        assert_eq!(EqAffine::from_uncompressed(&bytes).unwrap(), p);
+        assert_eq!(EqAffine::from_uncompressed_unchecked(&bytes).unwrap(), p);
+
+        let mut invalid = bytes;
+        invalid.0[32] ^= 1;
+        assert!(bool::from(EqAffine::from_uncompressed(&invalid).is_none()));
+        assert!(bool::from(
+            EqAffine::from_uncompressed_unchecked(&invalid).is_some()
+        ));
+    }
+
+    let identity = EqAffine::identity().to_uncompressed();
+    assert_eq!(identity, UncompressedPoint::default());
+    assert_eq!(
+        EqAffine::from_uncompressed(&identity).unwrap(),
+        EqAffine::identity()
+    );
+    assert_eq!((Eq::identity().to_affine()).to_uncompressed(), identity);
+
+    let mut non_canonical = identity;
+    non_canonical.0[..32].fill(0xff);
+    assert!(bool::from(
+        EqAffine::from_uncompressed_unchecked(&non_canonical).is_none()
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_sec1_encoding() {
+    use crate::DecodeError;
+    use ff::PrimeField;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Eq::random(&mut rng).to_affine();
+        let mut x = p.x().to_repr();
+        x.reverse();
+
+        let compressed = p.to_sec1_bytes(true);
+        assert_eq!(compressed.len(), 33);
+        assert_eq!(compressed[0], 0x02 | p.y().is_odd().unwrap_u8());
+        assert_eq!(&compressed[1..], &x);
+        assert_eq!(EqAffine::from_sec1_bytes(&compressed), Ok(p));
+
+        let uncompressed = p.to_sec1_bytes(false);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(uncompressed[0], 0x04);
+        assert_eq!(&uncompressed[1..33], &x);
+        assert_eq!(EqAffine::from_sec1_bytes(&uncompressed), Ok(p));
+
+        let mut invalid = uncompressed;
+        invalid[64] ^= 1;
+        assert_eq!(
+            EqAffine::from_sec1_bytes(&invalid),
+            Err(DecodeError::NotOnCurve)
+        );
+    }
+
+    let identity = EqAffine::identity();
+    assert_eq!(identity.to_sec1_bytes(true), [0x00]);
+    assert_eq!(identity.to_sec1_bytes(false), [0x00]);
+    assert_eq!(EqAffine::from_sec1_bytes(&[0x00]), Ok(identity));
+    let mut zero = [0u8; 65];
+    zero[0] = 0x04;
+    assert_eq!(
+        EqAffine::from_sec1_bytes(&zero),
+        Err(DecodeError::NotOnCurve)
+    );
+
+    assert_eq!(
+        EqAffine::from_sec1_bytes(&[]),
+        Err(DecodeError::InvalidHeader)
+    );
+    assert_eq!(
+        EqAffine::from_sec1_bytes(&[0x05; 33]),
+        Err(DecodeError::InvalidHeader)
+    );
+    assert_eq!(
+        EqAffine::from_sec1_bytes(&[0x02; 65]),
+        Err(DecodeError::InvalidLength {
+            expected: 33,
+            actual: 65
+        })
+    );
+    assert_eq!(
+        EqAffine::from_sec1_bytes(&[0x00, 0x00]),
+        Err(DecodeError::InvalidLength {
+            expected: 1,
+            actual: 2
+        })
+    );
+    let mut non_canonical = [0xff; 33];
+    non_canonical[0] = 0x02;
+    assert_eq!(
+        EqAffine::from_sec1_bytes(&non_canonical),
+        Err(DecodeError::NonCanonical)
+    );
+}
+
+#[test]
+fn test_from_bytes_reject_identity() {
+    use group::{Curve, Group, GroupEncoding};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let p = Eq::random(&mut rng);
+    let bytes = p.to_bytes();
+    assert_eq!(Eq::from_bytes_reject_identity(&bytes).unwrap(), p);
+    assert_eq!(
+        EqAffine::from_bytes_reject_identity(&bytes).unwrap(),
+        p.to_affine()
+    );
+
+    let identity = Eq::identity().to_bytes();
+    assert_eq!(identity, [0; 32]);
+    assert!(bool::from(Eq::from_bytes(&identity).is_some()));
+    assert!(bool::from(
+        Eq::from_bytes_reject_identity(&identity).is_none()
+    ));
+    assert!(bool::from(
+        EqAffine::from_bytes_reject_identity(&identity).is_none()
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_from_bytes() {
+    use group::{Curve, Group, GroupEncoding};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::vec::Vec;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut bytes: Vec<[u8; 32]> = (0..100).map(|_| Eq::random(&mut rng).to_bytes()).collect();
+    bytes[10] = Eq::identity().to_bytes();
+    bytes[20] = [0xff; 32];
+
+    let points = EqAffine::batch_from_bytes(&bytes);
+    assert_eq!(points.len(), bytes.len());
+    for (p, bytes) in points.iter().zip(bytes.iter()) {
+        let expected = EqAffine::from_bytes(bytes);
+        assert_eq!(bool::from(p.is_some()), bool::from(expected.is_some()));
+        if let Some(p) = Option::<EqAffine>::from(*p) {
+            assert_eq!(p, expected.unwrap());
+            assert_eq!(p.to_bytes(), *bytes);
+        }
+    }
+    assert!(bool::from(points[20].is_none()));
+    assert_eq!(points[10].unwrap(), Eq::identity().to_affine());
+}
+
+#[test]
+fn test_y_parity() {
+    use group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use subtle::Choice;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let p = Eq::random(&mut rng).to_affine();
+        assert_eq!(p.y_is_odd().unwrap_u8(), p.to_bytes()[31] >> 7);
+        assert_eq!(EqAffine::from_x_and_parity(p.x(), p.y_is_odd()).unwrap(), p);
+        assert_eq!(
+            EqAffine::from_x_and_parity(p.x(), !p.y_is_odd()).unwrap(),
+            -p
+        );
+    }
+    assert!(!bool::from(EqAffine::identity().y_is_odd()));
+
+    let x = Eq::random(&mut rng).to_affine().x();
+    assert!(bool::from(
+        EqAffine::from_x_and_parity(x, Choice::from(0)).is_some()
+    ));
+    // About half of all x-coordinates are not on the curve.
+    let mut x = x;
+    while bool::from(EqAffine::from_x_and_parity(x, Choice::from(0)).is_some()) {
+        x += Fq::one();
+    }
+    assert!(bool::from(
+        EqAffine::from_x_and_parity(x, Choice::from(1)).is_none()
+    ));
+}
+
+#[test]
+fn test_curve_constants() {
+    use ff::Field;
+
+    assert_eq!(Eq::A, Fq::zero());
+    assert_eq!(Eq::B, Fq::from(5));
+    assert_eq!(Eq::j_invariant(), Fq::zero());
+    assert_eq!(Eq::ORDER, Fp::MODULUS_LIMBS);
+    let mut order_minus_one = Eq::ORDER;
+    order_minus_one[0] -= 1;
+    assert_eq!(Fp::from_raw(order_minus_one), -Fp::one());
+
+    assert!(!bool::from(Eq::ISO_A.is_zero()));
+    assert!(!bool::from(Eq::ISO_B.is_zero()));
+    assert!(!bool::from(crate::curves::IsoEq::j_invariant().is_zero()));
+}